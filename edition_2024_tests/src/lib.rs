@@ -0,0 +1 @@
+//! Positive tests of `prudent` with edition-2024-only syntax. See `../README.md`.
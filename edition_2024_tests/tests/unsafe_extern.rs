@@ -0,0 +1,46 @@
+//! `unsafe_fn` with items of an edition 2024 `unsafe extern` block.
+use core::ffi::{c_char, c_int};
+use prudent::prelude::unsafe_fn;
+
+unsafe extern "C" {
+    safe fn abs(value: c_int) -> c_int;
+    unsafe fn strlen(string: *const c_char) -> usize;
+}
+
+type StrLen = unsafe extern "C" fn(*const c_char) -> usize;
+
+struct CLibrary {
+    strlen: StrLen,
+}
+
+#[test]
+fn unsafe_extern_fn_path() {
+    assert_eq!(unsafe_fn!(strlen; c"four".as_ptr()), 4);
+}
+
+#[test]
+fn unsafe_extern_fn_ptr_field() {
+    let library = CLibrary { strlen };
+    assert_eq!(unsafe_fn!(library.strlen; c"prudent".as_ptr()), 7);
+}
+
+#[test]
+fn unsafe_extern_fn_ptr_index() {
+    let table: [StrLen; 1] = [strlen];
+    assert_eq!(unsafe_fn!(table[0]; c"".as_ptr()), 0);
+}
+
+#[test]
+fn unsafe_extern_fn_ptr_option() {
+    let table: [StrLen; 1] = [strlen];
+    let maybe: Option<StrLen> = table.first().copied();
+    assert_eq!(unsafe_fn!(maybe.unwrap(); c"abc".as_ptr()), 3);
+}
+
+/// A `safe fn` item needs no `unsafe` (and hence no `prudent`) at all.
+#[test]
+fn safe_extern_fn_direct() {
+    assert_eq!(abs(-5), 5);
+    let as_pointer: extern "C" fn(c_int) -> c_int = abs;
+    assert_eq!(as_pointer(-6), 6);
+}
@@ -0,0 +1,15 @@
+use core::ffi::c_int;
+use prudent::prelude::unsafe_fn;
+
+unsafe extern "C" {
+    safe fn abs(value: c_int) -> c_int;
+}
+
+struct CLibrary {
+    abs: extern "C" fn(c_int) -> c_int,
+}
+
+fn main() {
+    let library = CLibrary { abs };
+    let _ = unsafe_fn!(library.abs; -1);
+}
@@ -0,0 +1,11 @@
+use core::ffi::c_int;
+use prudent::prelude::unsafe_fn;
+
+unsafe extern "C" {
+    safe fn abs(value: c_int) -> c_int;
+}
+
+fn main() {
+    let table: [extern "C" fn(c_int) -> c_int; 1] = [abs];
+    let _ = unsafe_fn!(table[0]; -1);
+}
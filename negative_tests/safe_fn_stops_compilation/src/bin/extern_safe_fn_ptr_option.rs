@@ -0,0 +1,11 @@
+use core::ffi::c_int;
+use prudent::prelude::unsafe_fn;
+
+unsafe extern "C" {
+    safe fn abs(value: c_int) -> c_int;
+}
+
+fn main() {
+    let maybe: Option<extern "C" fn(c_int) -> c_int> = Some(abs);
+    let _ = unsafe_fn!(maybe.unwrap(); -1);
+}
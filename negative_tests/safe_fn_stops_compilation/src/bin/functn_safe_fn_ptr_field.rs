@@ -0,0 +1,14 @@
+use prudent::prelude::unsafe_fn;
+
+fn init(value: u32) -> u32 {
+    value
+}
+
+struct VTable {
+    init: fn(u32) -> u32,
+}
+
+fn main() {
+    let table = VTable { init };
+    let _ = unsafe_fn!(table.init; 1);
+}
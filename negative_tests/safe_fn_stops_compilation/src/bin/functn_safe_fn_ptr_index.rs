@@ -0,0 +1,11 @@
+use prudent::prelude::unsafe_fn;
+
+fn double(value: u32) -> u32 {
+    value * 2
+}
+
+fn main() {
+    let handlers: [fn(u32) -> u32; 1] = [double];
+    let i = 0;
+    let _ = unsafe_fn!(handlers[i]; 1);
+}
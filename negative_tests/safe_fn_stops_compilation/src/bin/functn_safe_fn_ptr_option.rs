@@ -0,0 +1,10 @@
+use prudent::prelude::unsafe_fn;
+
+fn reset(a: u32, b: u32) -> u32 {
+    a + b
+}
+
+fn main() {
+    let maybe_reset: Option<fn(u32, u32) -> u32> = Some(reset);
+    let _ = unsafe_fn!(maybe_reset.unwrap(); 1, 2);
+}
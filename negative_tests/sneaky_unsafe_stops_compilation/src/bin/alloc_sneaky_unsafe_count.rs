@@ -0,0 +1,10 @@
+use prudent::prelude::{unsafe_alloc, unsafe_dealloc};
+
+unsafe fn count() -> usize {
+    1
+}
+
+fn main() {
+    let items = unsafe_alloc!(type [u8; count()]);
+    unsafe_dealloc!(type [u8], items);
+}
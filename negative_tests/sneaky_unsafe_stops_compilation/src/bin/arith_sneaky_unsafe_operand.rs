@@ -0,0 +1,10 @@
+use prudent::prelude::unsafe_arith;
+
+unsafe fn offset() -> u32 {
+    1
+}
+
+fn main() {
+    let base = 10u32;
+    let _ = unsafe_arith!(base * 2 + offset());
+}
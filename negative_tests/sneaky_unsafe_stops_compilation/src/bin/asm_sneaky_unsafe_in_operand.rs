@@ -0,0 +1,9 @@
+use prudent::prelude::unsafe_asm;
+
+unsafe fn value() -> u64 {
+    1
+}
+
+fn main() {
+    unsafe_asm!("/* {} */", in(reg) value());
+}
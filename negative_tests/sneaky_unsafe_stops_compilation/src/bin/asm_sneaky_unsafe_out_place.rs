@@ -0,0 +1,7 @@
+use prudent::prelude::unsafe_asm;
+
+static mut VALUE: u64 = 0;
+
+fn main() {
+    unsafe_asm!("/* {} */", out(reg) VALUE);
+}
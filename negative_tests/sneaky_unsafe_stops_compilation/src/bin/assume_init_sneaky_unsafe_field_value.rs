@@ -0,0 +1,18 @@
+use core::mem::MaybeUninit;
+use prudent::prelude::unsafe_assume_init;
+
+struct Point {
+    x: i32,
+    y: i32,
+}
+
+unsafe fn coordinate() -> i32 {
+    1
+}
+
+fn main() {
+    let _ = unsafe_assume_init!(MaybeUninit::<Point>::uninit() => Point {
+        x: 0,
+        y: coordinate(),
+    });
+}
@@ -0,0 +1,10 @@
+use prudent::prelude::unsafe_borrow_raw_fd;
+use std::os::fd::RawFd;
+
+unsafe fn fd() -> RawFd {
+    0
+}
+
+fn main() {
+    let _ = unsafe_borrow_raw_fd!(fd());
+}
@@ -0,0 +1,9 @@
+use prudent::prelude::unsafe_cast;
+
+unsafe fn ptr() -> *const u32 {
+    core::ptr::null()
+}
+
+fn main() {
+    let _ = unsafe_cast!(ptr(), u32 => i32);
+}
@@ -0,0 +1,10 @@
+use core::cell::Cell;
+use prudent::prelude::unsafe_cell_mut;
+
+unsafe fn cell() -> &'static Cell<u8> {
+    Box::leak(Box::new(Cell::new(0)))
+}
+
+fn main() {
+    let _ = unsafe_cell_mut!(cell());
+}
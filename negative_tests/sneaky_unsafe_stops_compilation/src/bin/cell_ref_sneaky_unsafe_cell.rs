@@ -0,0 +1,10 @@
+use core::cell::UnsafeCell;
+use prudent::prelude::unsafe_cell_ref;
+
+unsafe fn cell() -> &'static UnsafeCell<u8> {
+    Box::leak(Box::new(UnsafeCell::new(0)))
+}
+
+fn main() {
+    let _ = unsafe_cell_ref!(cell());
+}
@@ -0,0 +1,14 @@
+use prudent::prelude::unsafe_container_of;
+
+struct Task {
+    id: u32,
+}
+
+unsafe fn id_ptr(task: &Task) -> *const u32 {
+    &task.id
+}
+
+fn main() {
+    let task = Task { id: 1 };
+    let _ = unsafe_container_of!(id_ptr(&task), Task, id);
+}
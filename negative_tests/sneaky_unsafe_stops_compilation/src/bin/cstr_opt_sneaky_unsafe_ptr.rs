@@ -0,0 +1,10 @@
+use core::ffi::c_char;
+use prudent::prelude::unsafe_cstr_opt;
+
+unsafe fn ptr() -> *const c_char {
+    core::ptr::null()
+}
+
+fn main() {
+    let _ = unsafe_cstr_opt!(ptr());
+}
@@ -0,0 +1,9 @@
+use prudent::prelude::unsafe_cstr;
+
+unsafe fn bytes() -> &'static [u8] {
+    b"\0"
+}
+
+fn main() {
+    let _ = unsafe_cstr!(bytes_with_nul = bytes());
+}
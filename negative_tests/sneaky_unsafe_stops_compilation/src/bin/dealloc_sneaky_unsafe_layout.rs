@@ -0,0 +1,11 @@
+use core::alloc::Layout;
+use prudent::prelude::{unsafe_alloc, unsafe_dealloc};
+
+unsafe fn layout() -> Layout {
+    Layout::new::<u8>()
+}
+
+fn main() {
+    let ptr = unsafe_alloc!(Layout::new::<u8>());
+    unsafe_dealloc!(ptr, layout());
+}
@@ -0,0 +1,18 @@
+use prudent::prelude::unsafe_extern_static;
+
+mod foreign {
+    #[unsafe(no_mangle)]
+    pub static PRUDENT_SNEAKY_TABLE: [u8; 2] = [1, 2];
+}
+
+unsafe extern "C" {
+    static PRUDENT_SNEAKY_TABLE: [u8; 2];
+}
+
+unsafe fn index() -> usize {
+    0
+}
+
+fn main() {
+    let _ = unsafe_extern_static!(PRUDENT_SNEAKY_TABLE => [index()]);
+}
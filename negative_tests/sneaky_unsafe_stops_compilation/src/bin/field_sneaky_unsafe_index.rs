@@ -0,0 +1,10 @@
+use prudent::prelude::unsafe_field;
+
+unsafe fn index() -> usize {
+    0
+}
+
+fn main() {
+    let values = [1u8, 2];
+    let _ = unsafe_field!(&values as *const [u8; 2] => [index()]);
+}
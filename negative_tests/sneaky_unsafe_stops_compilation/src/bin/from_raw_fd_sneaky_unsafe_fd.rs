@@ -0,0 +1,10 @@
+use prudent::prelude::unsafe_from_raw_fd;
+use std::os::fd::{OwnedFd, RawFd};
+
+unsafe fn fd() -> RawFd {
+    0
+}
+
+fn main() {
+    let _ = unsafe_from_raw_fd!(OwnedFd, fd());
+}
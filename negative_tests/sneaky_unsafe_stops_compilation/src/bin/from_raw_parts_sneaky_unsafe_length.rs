@@ -0,0 +1,11 @@
+use core::mem::ManuallyDrop;
+use prudent::prelude::unsafe_from_raw_parts;
+
+unsafe fn length() -> usize {
+    0
+}
+
+fn main() {
+    let mut bytes = ManuallyDrop::new(vec![1u8]);
+    let _ = unsafe_from_raw_parts!(Vec, bytes.as_mut_ptr(), length(), bytes.capacity());
+}
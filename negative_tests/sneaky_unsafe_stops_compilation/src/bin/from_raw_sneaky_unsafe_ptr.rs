@@ -0,0 +1,9 @@
+use prudent::prelude::unsafe_from_raw;
+
+unsafe fn ptr() -> *mut u8 {
+    Box::into_raw(Box::new(1))
+}
+
+fn main() {
+    let _ = unsafe_from_raw!(Box, ptr());
+}
@@ -0,0 +1,13 @@
+use prudent::prelude::unsafe_fn_async;
+
+async unsafe fn first(bytes: &[u8]) -> u8 {
+    unsafe { *bytes.get_unchecked(0) }
+}
+
+async fn caller() -> u8 {
+    unsafe_fn_async!(first; core::str::from_utf8_unchecked(b"G'Day").as_bytes()).await
+}
+
+fn main() {
+    let _future = caller();
+}
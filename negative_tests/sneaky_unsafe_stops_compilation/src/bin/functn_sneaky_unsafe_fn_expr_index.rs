@@ -0,0 +1,14 @@
+use prudent::prelude::unsafe_fn;
+
+unsafe fn double(value: u32) -> u32 {
+    value * 2
+}
+
+unsafe fn index() -> usize {
+    0
+}
+
+fn main() {
+    let handlers: [unsafe fn(u32) -> u32; 1] = [double];
+    let _ = unsafe_fn!(handlers[index()]; 1);
+}
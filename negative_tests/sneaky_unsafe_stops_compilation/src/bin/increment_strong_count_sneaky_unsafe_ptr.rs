@@ -0,0 +1,10 @@
+use prudent::prelude::unsafe_increment_strong_count;
+use std::sync::Arc;
+
+unsafe fn ptr() -> *const u8 {
+    Arc::into_raw(Arc::new(1))
+}
+
+fn main() {
+    unsafe_increment_strong_count!(Arc, ptr());
+}
@@ -0,0 +1,19 @@
+use prudent::prelude::unsafe_method_async;
+
+struct S;
+
+impl S {
+    async unsafe fn method(&self) {}
+}
+
+unsafe fn new_receiver() -> S {
+    unreachable!()
+}
+
+async fn caller() {
+    unsafe_method_async!(new_receiver() =>. method).await
+}
+
+fn main() {
+    let _future = caller();
+}
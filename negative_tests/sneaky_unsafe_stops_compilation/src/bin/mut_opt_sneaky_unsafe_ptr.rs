@@ -0,0 +1,9 @@
+use prudent::prelude::unsafe_mut_opt;
+
+unsafe fn ptr() -> *mut u8 {
+    core::ptr::null_mut()
+}
+
+fn main() {
+    let _ = unsafe_mut_opt!(ptr());
+}
@@ -0,0 +1,11 @@
+use core::marker::PhantomPinned;
+use core::pin::Pin;
+use prudent::prelude::unsafe_pin_get_mut;
+
+unsafe fn pin() -> Pin<&'static mut PhantomPinned> {
+    Pin::static_mut(Box::leak(Box::new(PhantomPinned)))
+}
+
+fn main() {
+    let _ = unsafe_pin_get_mut!(pin());
+}
@@ -0,0 +1,22 @@
+use core::marker::PhantomPinned;
+use core::mem::ManuallyDrop;
+use core::pin::pin;
+use prudent::prelude::unsafe_pin_map;
+
+union Either {
+    pinned: ManuallyDrop<PhantomPinned>,
+    count: u32,
+}
+
+struct Pinned {
+    either: Either,
+    _pinned: PhantomPinned,
+}
+
+fn main() {
+    let mut pinned = pin!(Pinned {
+        either: Either { count: 0 },
+        _pinned: PhantomPinned,
+    });
+    let _ = unsafe_pin_map!(mut pinned.as_mut(), |value| &mut *value.either.pinned);
+}
@@ -0,0 +1,17 @@
+use core::marker::PhantomPinned;
+use core::pin::Pin;
+use prudent::prelude::unsafe_pin_map;
+
+struct Pinned {
+    inner: PhantomPinned,
+}
+
+unsafe fn pin() -> Pin<&'static Pinned> {
+    Pin::static_ref(Box::leak(Box::new(Pinned {
+        inner: PhantomPinned,
+    })))
+}
+
+fn main() {
+    let _ = unsafe_pin_map!(pin() => .inner);
+}
@@ -0,0 +1,10 @@
+use core::marker::PhantomPinned;
+use prudent::prelude::unsafe_pin_new;
+
+unsafe fn pointer() -> &'static mut PhantomPinned {
+    Box::leak(Box::new(PhantomPinned))
+}
+
+fn main() {
+    let _ = unsafe_pin_new!(pointer());
+}
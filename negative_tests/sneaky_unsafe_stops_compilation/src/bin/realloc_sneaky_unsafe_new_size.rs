@@ -0,0 +1,13 @@
+use core::alloc::Layout;
+use prudent::prelude::{unsafe_alloc, unsafe_dealloc, unsafe_realloc};
+
+unsafe fn new_size() -> usize {
+    2
+}
+
+fn main() {
+    let layout = Layout::new::<u8>();
+    let ptr = unsafe_alloc!(layout);
+    let ptr = unsafe_realloc!(ptr, layout, new_size());
+    unsafe_dealloc!(ptr, Layout::new::<[u8; 2]>());
+}
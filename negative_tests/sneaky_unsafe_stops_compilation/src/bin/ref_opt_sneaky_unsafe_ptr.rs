@@ -0,0 +1,9 @@
+use prudent::prelude::unsafe_ref_opt;
+
+unsafe fn ptr() -> *const u8 {
+    core::ptr::null_mut()
+}
+
+fn main() {
+    let _ = unsafe_ref_opt!(ptr());
+}
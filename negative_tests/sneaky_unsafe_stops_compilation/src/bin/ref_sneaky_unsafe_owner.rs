@@ -0,0 +1,10 @@
+use prudent::prelude::unsafe_ref;
+
+unsafe fn owner() -> &'static [u8; 1] {
+    &[1]
+}
+
+fn main() {
+    let pt: *const [u8; 1] = &[1];
+    let _ = unsafe_ref!(pt, borrowing = owner());
+}
@@ -0,0 +1,9 @@
+use prudent::prelude::unsafe_remove_var;
+
+unsafe fn key() -> &'static str {
+    "PRUDENT_SNEAKY"
+}
+
+fn main() {
+    unsafe_remove_var!(key(), single_threaded);
+}
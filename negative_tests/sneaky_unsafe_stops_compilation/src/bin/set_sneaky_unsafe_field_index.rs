@@ -0,0 +1,15 @@
+use prudent::prelude::unsafe_set;
+
+struct Header {
+    checksums: [u32; 2],
+}
+
+unsafe fn index() -> usize {
+    0
+}
+
+fn main() {
+    let mut header = Header { checksums: [0; 2] };
+    let pt: *mut Header = &mut header;
+    unsafe_set!(pt => .checksums[index()], 1);
+}
@@ -0,0 +1,9 @@
+use prudent::prelude::unsafe_set_var;
+
+unsafe fn value() -> &'static str {
+    "sneaky"
+}
+
+fn main() {
+    unsafe_set_var!("PRUDENT_SNEAKY", value());
+}
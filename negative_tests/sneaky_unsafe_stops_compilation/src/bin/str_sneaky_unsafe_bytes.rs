@@ -0,0 +1,9 @@
+use prudent::prelude::unsafe_str;
+
+unsafe fn bytes() -> &'static [u8] {
+    b"prudent"
+}
+
+fn main() {
+    let _ = unsafe_str!(bytes());
+}
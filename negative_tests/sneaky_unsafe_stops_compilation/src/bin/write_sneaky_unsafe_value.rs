@@ -0,0 +1,11 @@
+use prudent::prelude::unsafe_write;
+
+unsafe fn value() -> u32 {
+    1
+}
+
+fn main() {
+    let mut x = 0u32;
+    let pt: *mut u32 = &mut x;
+    unsafe_write!(pt, value());
+}
@@ -0,0 +1,8 @@
+use core::pin::Pin;
+use prudent::prelude::unsafe_pin_get_mut;
+
+fn main() {
+    let mut count = 0u32;
+    let pin = Pin::new(&mut count);
+    let _ = unsafe_pin_get_mut!(pin);
+}
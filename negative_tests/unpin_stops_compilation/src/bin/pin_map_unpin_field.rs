@@ -0,0 +1,16 @@
+use core::marker::PhantomPinned;
+use core::pin::pin;
+use prudent::prelude::unsafe_pin_map;
+
+struct Counter {
+    count: u32,
+    _pinned: PhantomPinned,
+}
+
+fn main() {
+    let mut counter = pin!(Counter {
+        count: 0,
+        _pinned: PhantomPinned,
+    });
+    let _ = unsafe_pin_map!(mut counter.as_mut() => .count);
+}
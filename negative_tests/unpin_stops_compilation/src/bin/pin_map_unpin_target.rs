@@ -0,0 +1,16 @@
+use core::marker::PhantomPinned;
+use core::pin::Pin;
+use prudent::prelude::unsafe_pin_map;
+
+struct Wrapper {
+    inner: PhantomPinned,
+}
+// With this, the field must not be structurally pinned.
+impl Unpin for Wrapper {}
+
+fn main() {
+    let mut wrapper = Wrapper {
+        inner: PhantomPinned,
+    };
+    let _ = unsafe_pin_map!(mut Pin::new(&mut wrapper) => .inner);
+}
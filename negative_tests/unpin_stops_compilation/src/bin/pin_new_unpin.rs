@@ -0,0 +1,6 @@
+use prudent::prelude::unsafe_pin_new;
+
+fn main() {
+    let mut count = 0u32;
+    let _ = unsafe_pin_new!(&mut count);
+}
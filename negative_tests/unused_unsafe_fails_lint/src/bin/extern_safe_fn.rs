@@ -0,0 +1,11 @@
+#![forbid(unused_unsafe)]
+use core::ffi::c_int;
+use prudent::prelude::unsafe_fn;
+
+unsafe extern "C" {
+    safe fn abs(value: c_int) -> c_int;
+}
+
+fn main() {
+    let _ = unsafe_fn!(abs; -1);
+}
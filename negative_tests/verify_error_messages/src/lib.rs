@@ -21,3 +21,9 @@ fn unused_unsafe_fails_lint_method_some_args() {
     let t = trybuild::TestCases::new();
     t.compile_fail("../unused_unsafe_fails_lint/src/bin/method_some_args.rs");
 }
+
+#[test]
+fn unused_unsafe_fails_lint_extern_safe_fn() {
+    let t = trybuild::TestCases::new();
+    t.compile_fail("../unused_unsafe_fails_lint/src/bin/extern_safe_fn.rs");
+}
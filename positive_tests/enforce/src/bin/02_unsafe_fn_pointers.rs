@@ -0,0 +1 @@
+../../../shared/src/bin/02_unsafe_fn_pointers.rs
\ No newline at end of file
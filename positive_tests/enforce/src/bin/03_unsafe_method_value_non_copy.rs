@@ -1 +0,0 @@
-../../../shared/src/bin/03_unsafe_method_value_non_copy.rs
\ No newline at end of file
@@ -0,0 +1 @@
+../../../shared/src/bin/04_unsafe_method_value_non_copy.rs
\ No newline at end of file
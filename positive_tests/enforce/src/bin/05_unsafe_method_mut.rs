@@ -1 +0,0 @@
-../../../shared/src/bin/05_unsafe_method_mut.rs
\ No newline at end of file
@@ -1 +0,0 @@
-../../../shared/src/bin/02_unsafe_method_value_copy.rs
\ No newline at end of file
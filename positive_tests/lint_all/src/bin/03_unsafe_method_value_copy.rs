@@ -0,0 +1 @@
+../../../shared/src/bin/03_unsafe_method_value_copy.rs
\ No newline at end of file
@@ -0,0 +1 @@
+../../../shared/src/bin/05_unsafe_method_ref.rs
\ No newline at end of file
@@ -1 +0,0 @@
-../../../shared/src/bin/04_unsafe_method_ref.rs
\ No newline at end of file
@@ -0,0 +1 @@
+../../../shared/src/bin/06_unsafe_method_mut.rs
\ No newline at end of file
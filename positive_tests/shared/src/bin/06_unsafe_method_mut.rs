@@ -0,0 +1 @@
+mce::nth_by_file!("../mce_config.toml" @ 6);
@@ -1,5 +1,21 @@
 //! "backend" functionality (anything else than macros).
 
+/// Macros implemented by `prudent-macros-enforce` (or by `prudent-macros-lint` with feature
+/// `lint_unused_unsafe`). Re-exported by [crate::prelude], and invoked by our own `macro_rules!`
+/// macros that build on them.
+///
+/// Internal - NOT a part of public API!
+#[doc(hidden)]
+#[cfg(not(feature = "lint_unused_unsafe"))]
+pub use prudent_macros_enforce as prudent_macros;
+
+/// See the above.
+///
+/// Internal - NOT a part of public API!
+#[doc(hidden)]
+#[cfg(feature = "lint_unused_unsafe")]
+pub use prudent_macros_lint as prudent_macros;
+
 #[doc(hidden)]
 #[inline]
 pub const fn assert_version(expected_version: &'static str) {
@@ -55,6 +71,190 @@ pub trait FailsWithConflictForSafeFunction {
 }
 impl<T> FailsWithConflictForSafeFunction for T {}
 
+/// See [ExpectedUnsafeFnPointerButReceivedSafe::prudent_conflict_for_safe_fn_pointer].
+///
+/// Internal - NOT a part of public API!
+#[doc(hidden)]
+pub trait ExpectedUnsafeFnPointerButReceivedSafe<Args> {
+    /// Triggers `multiple applicable items in scope` error if you invoke it on a function pointer
+    /// (or a function, or a closure) that **is** safe and that has any number of arguments (up to
+    /// 12). That includes safe `extern "C"` (and `"C-unwind"`, `"system"`, `"system-unwind"`)
+    /// function pointers. Used by [crate::prelude::unsafe_fn] when its `$fn` is an expression
+    /// (rather than a path).
+    ///
+    /// Internal - NOT a part of public API!
+    fn prudent_conflict_for_safe_fn_pointer(&self) {}
+}
+impl<O, F: Fn() -> O> ExpectedUnsafeFnPointerButReceivedSafe<()> for F {}
+impl<A1, O, F: Fn(A1) -> O> ExpectedUnsafeFnPointerButReceivedSafe<(A1,)> for F {}
+impl<A1, A2, O, F: Fn(A1, A2) -> O> ExpectedUnsafeFnPointerButReceivedSafe<(A1, A2)> for F {}
+impl<A1, A2, A3, O, F: Fn(A1, A2, A3) -> O> ExpectedUnsafeFnPointerButReceivedSafe<(A1, A2, A3)>
+    for F
+{
+}
+impl<A1, A2, A3, A4, O, F: Fn(A1, A2, A3, A4) -> O>
+    ExpectedUnsafeFnPointerButReceivedSafe<(A1, A2, A3, A4)> for F
+{
+}
+impl<A1, A2, A3, A4, A5, O, F: Fn(A1, A2, A3, A4, A5) -> O>
+    ExpectedUnsafeFnPointerButReceivedSafe<(A1, A2, A3, A4, A5)> for F
+{
+}
+impl<A1, A2, A3, A4, A5, A6, O, F: Fn(A1, A2, A3, A4, A5, A6) -> O>
+    ExpectedUnsafeFnPointerButReceivedSafe<(A1, A2, A3, A4, A5, A6)> for F
+{
+}
+impl<A1, A2, A3, A4, A5, A6, A7, O, F: Fn(A1, A2, A3, A4, A5, A6, A7) -> O>
+    ExpectedUnsafeFnPointerButReceivedSafe<(A1, A2, A3, A4, A5, A6, A7)> for F
+{
+}
+impl<A1, A2, A3, A4, A5, A6, A7, A8, O, F: Fn(A1, A2, A3, A4, A5, A6, A7, A8) -> O>
+    ExpectedUnsafeFnPointerButReceivedSafe<(A1, A2, A3, A4, A5, A6, A7, A8)> for F
+{
+}
+impl<A1, A2, A3, A4, A5, A6, A7, A8, A9, O, F: Fn(A1, A2, A3, A4, A5, A6, A7, A8, A9) -> O>
+    ExpectedUnsafeFnPointerButReceivedSafe<(A1, A2, A3, A4, A5, A6, A7, A8, A9)> for F
+{
+}
+impl<
+        A1,
+        A2,
+        A3,
+        A4,
+        A5,
+        A6,
+        A7,
+        A8,
+        A9,
+        A10,
+        O,
+        F: Fn(A1, A2, A3, A4, A5, A6, A7, A8, A9, A10) -> O,
+    > ExpectedUnsafeFnPointerButReceivedSafe<(A1, A2, A3, A4, A5, A6, A7, A8, A9, A10)> for F
+{
+}
+impl<
+        A1,
+        A2,
+        A3,
+        A4,
+        A5,
+        A6,
+        A7,
+        A8,
+        A9,
+        A10,
+        A11,
+        O,
+        F: Fn(A1, A2, A3, A4, A5, A6, A7, A8, A9, A10, A11) -> O,
+    > ExpectedUnsafeFnPointerButReceivedSafe<(A1, A2, A3, A4, A5, A6, A7, A8, A9, A10, A11)> for F
+{
+}
+impl<
+        A1,
+        A2,
+        A3,
+        A4,
+        A5,
+        A6,
+        A7,
+        A8,
+        A9,
+        A10,
+        A11,
+        A12,
+        O,
+        F: Fn(A1, A2, A3, A4, A5, A6, A7, A8, A9, A10, A11, A12) -> O,
+    > ExpectedUnsafeFnPointerButReceivedSafe<(A1, A2, A3, A4, A5, A6, A7, A8, A9, A10, A11, A12)>
+    for F
+{
+}
+
+/// Safe function pointers with an ABI other than `"Rust"` (for example, pointers to `safe fn` items
+/// of an edition 2024 `unsafe extern "C"` block) don't implement [Fn]. So they get their own
+/// implementations, here for any number of arguments (up to 12). Their `Args` is the function
+/// pointer type itself, so that they don't overlap with the above implementations.
+macro_rules! impl_expected_unsafe_fn_pointer_but_received_safe {
+    ($abi:literal) => {
+        impl_expected_unsafe_fn_pointer_but_received_safe!(@args $abi []);
+        impl_expected_unsafe_fn_pointer_but_received_safe!(@args $abi [A1]);
+        impl_expected_unsafe_fn_pointer_but_received_safe!(@args $abi [A1, A2]);
+        impl_expected_unsafe_fn_pointer_but_received_safe!(@args $abi [A1, A2, A3]);
+        impl_expected_unsafe_fn_pointer_but_received_safe!(@args $abi [A1, A2, A3, A4]);
+        impl_expected_unsafe_fn_pointer_but_received_safe!(@args $abi [A1, A2, A3, A4, A5]);
+        impl_expected_unsafe_fn_pointer_but_received_safe!(@args $abi [A1, A2, A3, A4, A5, A6]);
+        impl_expected_unsafe_fn_pointer_but_received_safe!(
+            @args $abi [A1, A2, A3, A4, A5, A6, A7]
+        );
+        impl_expected_unsafe_fn_pointer_but_received_safe!(
+            @args $abi [A1, A2, A3, A4, A5, A6, A7, A8]
+        );
+        impl_expected_unsafe_fn_pointer_but_received_safe!(
+            @args $abi [A1, A2, A3, A4, A5, A6, A7, A8, A9]
+        );
+        impl_expected_unsafe_fn_pointer_but_received_safe!(
+            @args $abi [A1, A2, A3, A4, A5, A6, A7, A8, A9, A10]
+        );
+        impl_expected_unsafe_fn_pointer_but_received_safe!(
+            @args $abi [A1, A2, A3, A4, A5, A6, A7, A8, A9, A10, A11]
+        );
+        impl_expected_unsafe_fn_pointer_but_received_safe!(
+            @args $abi [A1, A2, A3, A4, A5, A6, A7, A8, A9, A10, A11, A12]
+        );
+    };
+    (@args $abi:literal [$($arg:ident),*]) => {
+        impl<$($arg,)* O> ExpectedUnsafeFnPointerButReceivedSafe<extern $abi fn($($arg),*) -> O>
+            for extern $abi fn($($arg),*) -> O
+        {
+        }
+    };
+}
+impl_expected_unsafe_fn_pointer_but_received_safe!("C");
+impl_expected_unsafe_fn_pointer_but_received_safe!("C-unwind");
+impl_expected_unsafe_fn_pointer_but_received_safe!("system");
+impl_expected_unsafe_fn_pointer_but_received_safe!("system-unwind");
+
+/// See [ExpectedUnsafeFnPointerButReceivedSafe::prudent_conflict_for_safe_fn_pointer].
+///
+/// Internal - NOT a part of public API!
+#[doc(hidden)]
+pub trait FailsWithConflictForSafeFnPointer {
+    /// Triggers `multiple applicable items in scope` error if you invoke it on a function pointer
+    /// that **is** safe. Used by [crate::prelude::unsafe_fn].
+    ///
+    /// Internal - NOT a part of public API!
+    fn prudent_conflict_for_safe_fn_pointer(&self) {}
+}
+impl<T> FailsWithConflictForSafeFnPointer for T {}
+
+/// See [ExpectedNotUnpinButReceivedUnpin::prudent_conflict_for_unpin].
+///
+/// Internal - NOT a part of public API!
+#[doc(hidden)]
+pub trait ExpectedNotUnpinButReceivedUnpin {
+    /// Triggers `multiple applicable items in scope` error if you invoke it on a reference to a
+    /// type that **is** [Unpin]. (In generic code, where the type may or may not be [Unpin], this
+    /// compiles.) Used by [crate::prelude::unsafe_pin_new], [crate::prelude::unsafe_pin_get_mut]
+    /// and [crate::prelude::unsafe_pin_map], since for [Unpin] types there are safe alternatives.
+    ///
+    /// Internal - NOT a part of public API!
+    fn prudent_conflict_for_unpin(&self) {}
+}
+impl<T: Unpin + ?Sized> ExpectedNotUnpinButReceivedUnpin for T {}
+
+/// See [ExpectedNotUnpinButReceivedUnpin::prudent_conflict_for_unpin].
+///
+/// Internal - NOT a part of public API!
+#[doc(hidden)]
+pub trait FailsWithConflictForUnpin {
+    /// Triggers `multiple applicable items in scope` error if you invoke it on a reference to a
+    /// type that **is** [Unpin]. Used by [crate::prelude::unsafe_pin_new],
+    /// [crate::prelude::unsafe_pin_get_mut] and [crate::prelude::unsafe_pin_map].
+    ///
+    /// Internal - NOT a part of public API!
+    fn prudent_conflict_for_unpin(&self) {}
+}
+impl<T: ?Sized> FailsWithConflictForUnpin for T {}
+
 fn _try_unsafe_fn_zero_args() {
     (_safe_fun_bool as unsafe fn() -> bool).prudent_conflict_for_safe_function();
 
@@ -360,3 +560,341 @@ pub const fn shared_to_owned<T>(_: &T) -> T {
 /// Internal - NOT a part of public API!
 #[doc(hidden)]
 pub const fn expect_copy_ptr<T: Copy>(_: *const T) {}
+
+/// Pass a future through, unchanged. Used by [crate::prelude::unsafe_fn_async] and
+/// [crate::prelude::unsafe_method_async] to ensure (at compile time) that the invoked `unsafe`
+/// function/method is `async` (or that it otherwise returns a [core::future::Future]).
+///
+/// Internal - NOT a part of public API!
+#[doc(hidden)]
+#[inline(always)]
+pub fn expect_future<F: core::future::Future>(future: F) -> F {
+    future
+}
+
+/// Accept two raw pointers only if they have the same pointee type. Used by
+/// [crate::prelude::unsafe_container_of] (in an inactive `if false {...}` branch) to ensure (at
+/// compile time) that the given field pointer points to the type of the given field.
+///
+/// Internal - NOT a part of public API!
+#[doc(hidden)]
+#[inline(always)]
+pub const fn expect_same_pointee<T: ?Sized>(_field_ptr: *const T, _field_of_container: *const T) {}
+
+/// Pass a reference through, but shorten its lifetime to the lifetime of the given borrow of an
+/// owner. Used by [crate::prelude::unsafe_ref] (with `borrowing = ...`).
+///
+/// Internal - NOT a part of public API!
+#[doc(hidden)]
+#[inline(always)]
+pub const fn borrowing_ref<'a, O: ?Sized, T: ?Sized>(_owner: &'a O, reference: &'a T) -> &'a T {
+    reference
+}
+
+/// Like [borrowing_ref], but for a `mut` reference (to the content of the given cell). Used by
+/// [crate::prelude::unsafe_cell_mut].
+///
+/// Not `const`, because our MSRV doesn't allow `mut` references in `const fn`.
+///
+/// Internal - NOT a part of public API!
+#[doc(hidden)]
+#[inline(always)]
+pub fn borrowing_cell_mut<'a, C: ?Sized, T: ?Sized>(
+    _cell: &'a C,
+    reference: &'a mut T,
+) -> &'a mut T {
+    reference
+}
+
+/// A reference to a [core::cell::UnsafeCell] or to a [core::cell::Cell], to be converted to a raw
+/// pointer to its content. Used by [crate::prelude::unsafe_cell_ref] and
+/// [crate::prelude::unsafe_cell_mut].
+///
+/// Like [RawPointer], each conversion is an inherent `const fn`.
+///
+/// Internal - NOT a part of public API!
+#[doc(hidden)]
+pub struct CellReference<C>(pub C);
+
+impl<T: ?Sized> CellReference<&core::cell::UnsafeCell<T>> {
+    #[doc(hidden)]
+    #[inline(always)]
+    pub const fn into_mut_ptr(self) -> *mut T {
+        self.0.get()
+    }
+}
+
+impl<T: ?Sized> CellReference<&core::cell::Cell<T>> {
+    #[doc(hidden)]
+    #[inline(always)]
+    pub const fn into_mut_ptr(self) -> *mut T {
+        self.0.as_ptr()
+    }
+}
+
+/// A raw pointer (`*const T` or `*mut T`) or a [core::ptr::NonNull], to be converted to a raw
+/// pointer. Used by [crate::prelude::unsafe_ref], [crate::prelude::unsafe_mut],
+/// [crate::prelude::unsafe_val], [crate::prelude::unsafe_set] (and other macros that accept
+/// pointers), so that they accept [core::ptr::NonNull], too.
+///
+/// Each conversion is an inherent `const fn` (rather than a trait method), so that it works in
+/// `const` contexts. Which of the `impl` blocks applies is determined by the (known) type of the
+/// pointer.
+///
+/// Internal - NOT a part of public API!
+#[doc(hidden)]
+pub struct RawPointer<P>(pub P);
+
+impl<T: ?Sized> RawPointer<*const T> {
+    #[doc(hidden)]
+    #[inline(always)]
+    pub const fn into_const_ptr(self) -> *const T {
+        self.0
+    }
+}
+
+impl<T: ?Sized> RawPointer<*mut T> {
+    #[doc(hidden)]
+    #[inline(always)]
+    pub const fn into_const_ptr(self) -> *const T {
+        self.0 as *const T
+    }
+
+    #[doc(hidden)]
+    #[inline(always)]
+    pub const fn into_mut_ptr(self) -> *mut T {
+        self.0
+    }
+}
+
+impl<T: ?Sized> RawPointer<core::ptr::NonNull<T>> {
+    #[doc(hidden)]
+    #[inline(always)]
+    pub const fn into_const_ptr(self) -> *const T {
+        self.0.as_ptr() as *const T
+    }
+
+    #[doc(hidden)]
+    #[inline(always)]
+    pub const fn into_mut_ptr(self) -> *mut T {
+        self.0.as_ptr()
+    }
+}
+
+/// A `*const u8` or `*const i8`, to be converted to `*const` [core::ffi::c_char]. Used by
+/// [crate::prelude::unsafe_cstr] and [crate::prelude::unsafe_cstr_opt], so that they accept
+/// pointers to bytes (whether [core::ffi::c_char] is [u8] or [i8] on the target), but not pointers
+/// to other types.
+///
+/// Like [RawPointer], this uses inherent `const fn`s, so that it works in `const` contexts.
+///
+/// Internal - NOT a part of public API!
+#[doc(hidden)]
+pub struct CCharPointer<P>(pub P);
+
+impl CCharPointer<*const u8> {
+    #[doc(hidden)]
+    #[inline(always)]
+    pub const fn into_c_char_ptr(self) -> *const core::ffi::c_char {
+        self.0.cast()
+    }
+}
+
+impl CCharPointer<*const i8> {
+    #[doc(hidden)]
+    #[inline(always)]
+    pub const fn into_c_char_ptr(self) -> *const core::ffi::c_char {
+        self.0.cast()
+    }
+}
+
+/// Whether the given bytes end with nul, and contain no other nul. Used by
+/// [crate::prelude::unsafe_cstr] (with `bytes_with_nul = ...`) in debug builds.
+///
+/// Internal - NOT a part of public API!
+#[doc(hidden)]
+pub const fn is_bytes_with_nul(bytes: &[u8]) -> bool {
+    let Some((&last, rest)) = bytes.split_last() else {
+        return false;
+    };
+    let mut index = 0;
+    while index < rest.len() {
+        if rest[index] == 0 {
+            return false;
+        }
+        index += 1;
+    }
+    last == 0
+}
+
+/// Panic if the given bytes are not UTF-8, with a message that starts with `macro_name`, and that
+/// contains the offset of the first invalid byte. Used by [crate::prelude::unsafe_str] (and
+/// `unsafe_string!`) in debug builds.
+///
+/// This is a `const fn`, so it can't use [core::format_args] with numbers. Hence it composes the
+/// message in a local buffer.
+///
+/// Internal - NOT a part of public API!
+#[doc(hidden)]
+#[track_caller]
+pub const fn expect_utf8(bytes: &[u8], macro_name: &str) {
+    let offset = match core::str::from_utf8(bytes) {
+        Ok(_) => return,
+        Err(error) => error.valid_up_to(),
+    };
+    let parts: [&[u8]; 3] = [
+        macro_name.as_bytes(),
+        b": invalid UTF-8 at byte offset ",
+        b".",
+    ];
+    let mut digits = [0u8; 20];
+    let mut digits_start = digits.len();
+    let mut remaining = offset;
+    loop {
+        digits_start -= 1;
+        digits[digits_start] = b'0' + (remaining % 10) as u8;
+        remaining /= 10;
+        if remaining == 0 {
+            break;
+        }
+    }
+
+    let mut message = [0u8; 128];
+    let mut len = 0;
+    let mut part = 0;
+    while part < parts.len() {
+        if part == parts.len() - 1 {
+            // The offset goes just before the final period.
+            let mut index = digits_start;
+            while index < digits.len() && len < message.len() {
+                message[len] = digits[index];
+                len += 1;
+                index += 1;
+            }
+        }
+        let mut index = 0;
+        while index < parts[part].len() && len < message.len() {
+            message[len] = parts[part][index];
+            len += 1;
+            index += 1;
+        }
+        part += 1;
+    }
+    match core::str::from_utf8(message.split_at(len).0) {
+        Ok(message) => panic!("{}", message),
+        Err(_) => panic!("invalid UTF-8"),
+    }
+}
+
+/// The `alloc` crate, for macros that need `Vec`, `String`, `Box`... Only with feature `alloc`.
+///
+/// Internal - NOT a part of public API!
+#[doc(hidden)]
+#[cfg(feature = "alloc")]
+pub extern crate alloc;
+
+/// The `std` crate, for macros that need `std::env`... Only with feature `std`.
+///
+/// Internal - NOT a part of public API!
+#[doc(hidden)]
+#[cfg(feature = "std")]
+pub extern crate std;
+
+/// Panic if the current process has any thread other than the current one (and than the main
+/// thread), with a message that starts with `macro_name`. Used by `unsafe_set_var!` and
+/// `unsafe_remove_var!` (with `single_threaded`) in debug builds. Only with feature `std`.
+///
+/// The main thread is not counted if it's not the current one, because a test harness (like
+/// `libtest`) runs each test in a spawned thread, while its main thread only waits for it.
+///
+/// The number of threads comes from `/proc/self/status`, and whether the current thread is the
+/// main one comes from `/proc/thread-self`. So this checks only on Linux (and Android), and not
+/// under Miri (which, with isolation, aborts on opening a file). If `/proc/self/status` can't be
+/// read, this doesn't check either.
+///
+/// Internal - NOT a part of public API!
+#[doc(hidden)]
+#[cfg(feature = "std")]
+#[track_caller]
+pub fn assert_single_threaded(macro_name: &str) {
+    #[cfg(all(any(target_os = "linux", target_os = "android"), not(miri)))]
+    {
+        let Ok(status) = std::fs::read_to_string("/proc/self/status") else {
+            return;
+        };
+        let threads = status
+            .lines()
+            .find_map(|line| line.strip_prefix("Threads:"))
+            .and_then(|threads| threads.trim().parse::<usize>().ok());
+        // `/proc/thread-self` links to `<pid>/task/<tid>`, and the main thread's `tid` is `pid`.
+        // If it can't be read, count the main thread (so, be strict).
+        let current_is_main = std::fs::read_link("/proc/thread-self").map_or(true, |link| {
+            let parts: std::vec::Vec<&str> = link.to_str().unwrap_or_default().split('/').collect();
+            !matches!(parts[..], [pid, "task", tid] if pid != tid)
+        });
+        let allowed = if current_is_main { 1 } else { 2 };
+        if let Some(threads) = threads {
+            assert!(
+                threads <= allowed,
+                "{macro_name}: the process has {threads} threads, but it must be single-threaded \
+                 (other than a main thread that waits for the current one)."
+            );
+        }
+    }
+    #[cfg(not(all(any(target_os = "linux", target_os = "android"), not(miri))))]
+    let _ = macro_name;
+}
+
+/// Panic if the given file descriptor is not open, with a message that starts with `macro_name`.
+/// Used by `unsafe_from_raw_fd!` and `unsafe_borrow_raw_fd!` in debug builds. Only with feature
+/// `std`, and only on Unix.
+///
+/// This calls `fcntl(fd, F_GETFD)`, which fails (with `EBADF`) if `fd` is not open. It declares
+/// `fcntl` itself, so that `prudent` doesn't need the `libc` crate.
+///
+/// Internal - NOT a part of public API!
+#[doc(hidden)]
+#[cfg(all(feature = "std", unix))]
+#[track_caller]
+pub fn expect_open_fd(fd: std::os::fd::RawFd, macro_name: &str) {
+    use core::ffi::c_int;
+    extern "C" {
+        fn fcntl(fd: c_int, cmd: c_int, ...) -> c_int;
+    }
+    // The same on Linux, Android, macOS/iOS and the BSDs.
+    const F_GETFD: c_int = 1;
+
+    // SAFETY: F_GETFD only reads the flags of `fd`, and it takes no third argument.
+    let flags = unsafe { fcntl(fd, F_GETFD) };
+    assert!(
+        flags != -1,
+        "{macro_name}: file descriptor {fd} is not open."
+    );
+}
+
+/// Return the given projection closure, with its parameter type taken from `pin`. Used by
+/// [crate::prelude::unsafe_pin_map] (with `mut`), so that the closure is type-checked (and
+/// defined) **outside** `unsafe {...}`.
+///
+/// Internal - NOT a part of public API!
+#[doc(hidden)]
+#[inline(always)]
+pub fn pin_projection_mut<T: ?Sized, U: ?Sized, F: FnOnce(&mut T) -> &mut U>(
+    _pin: &core::pin::Pin<&mut T>,
+    projection: F,
+) -> F {
+    projection
+}
+
+/// Like [pin_projection_mut], but for shared pins. Used by [crate::prelude::unsafe_pin_map]
+/// (without `mut`).
+///
+/// Internal - NOT a part of public API!
+#[doc(hidden)]
+#[inline(always)]
+pub fn pin_projection_ref<T: ?Sized, U: ?Sized, F: FnOnce(&T) -> &U>(
+    _pin: &core::pin::Pin<&T>,
+    projection: F,
+) -> F {
+    projection
+}
@@ -2,6 +2,16 @@
 #[cfg(all(doctest, not(feature = "lint_unused_unsafe")))]
 pub mod sneaky_unsafe_stops_compilation;
 
+/// Doctests to verify that passing a safe function (pointer) to prudent stops compilation, even
+/// without feature `lint_unused_unsafe`.
+#[cfg(doctest)]
+pub mod safe_fn_stops_compilation;
+
+/// Doctests to verify that pinning (or projecting a pin to) an `Unpin` type with prudent stops
+/// compilation.
+#[cfg(doctest)]
+pub mod unpin_stops_compilation;
+
 /// Doctests to verify that using prudent unnecessarily fails "unused_unsafe" lint.
 #[cfg(all(doctest, feature = "lint_unused_unsafe"))]
 pub mod unused_unsafe_fails_lint;
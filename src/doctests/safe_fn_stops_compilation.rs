@@ -0,0 +1,7 @@
+pub mod extern_safe_fn_ptr_field;
+pub mod extern_safe_fn_ptr_index;
+pub mod extern_safe_fn_ptr_option;
+
+pub mod functn_safe_fn_ptr_field;
+pub mod functn_safe_fn_ptr_index;
+pub mod functn_safe_fn_ptr_option;
@@ -0,0 +1,4 @@
+/// ```compile_fail,E0034
+#[doc = include_str!("../../../negative_tests/safe_fn_stops_compilation/src/bin/functn_safe_fn_ptr_option.rs")]
+/// ```
+pub const _: () = {};
@@ -1,7 +1,74 @@
+#[cfg(feature = "alloc")]
+pub mod alloc_sneaky_unsafe_count;
+
+pub mod arith_sneaky_unsafe_operand;
+
+pub mod asm_sneaky_unsafe_in_operand;
+pub mod asm_sneaky_unsafe_out_place;
+
+pub mod assume_init_sneaky_unsafe_field_value;
+
+#[cfg(all(feature = "std", unix))]
+pub mod borrow_raw_fd_sneaky_unsafe_fd;
+
+pub mod cast_sneaky_unsafe_ptr;
+
+pub mod cell_mut_sneaky_unsafe_cell;
+pub mod cell_ref_sneaky_unsafe_cell;
+
+pub mod container_of_sneaky_unsafe_ptr;
+
+pub mod cstr_opt_sneaky_unsafe_ptr;
+pub mod cstr_sneaky_unsafe_bytes;
+
+#[cfg(feature = "alloc")]
+pub mod dealloc_sneaky_unsafe_layout;
+
+pub mod extern_static_sneaky_unsafe_index;
+
+pub mod field_sneaky_unsafe_index;
+
+#[cfg(all(feature = "std", unix))]
+pub mod from_raw_fd_sneaky_unsafe_fd;
+#[cfg(feature = "alloc")]
+pub mod from_raw_parts_sneaky_unsafe_length;
+#[cfg(feature = "alloc")]
+pub mod from_raw_sneaky_unsafe_ptr;
+
+pub mod functn_async_sneaky_unsafe_arg;
 pub mod functn_sneaky_unsafe_arg;
+pub mod functn_sneaky_unsafe_fn_expr_index;
 pub mod functn_sneaky_unsafe_fn_expr_none_args;
 pub mod functn_sneaky_unsafe_fn_expr_some_args;
 
+#[cfg(feature = "alloc")]
+pub mod increment_strong_count_sneaky_unsafe_ptr;
+
+pub mod method_async_sneaky_unsafe_self;
 pub mod method_sneaky_unsafe_arg;
 pub mod method_sneaky_unsafe_self_none_args;
 pub mod method_sneaky_unsafe_self_some_args;
+
+pub mod mut_opt_sneaky_unsafe_ptr;
+
+pub mod pin_get_mut_sneaky_unsafe_pin;
+pub mod pin_map_sneaky_unsafe_closure;
+pub mod pin_map_sneaky_unsafe_pin;
+pub mod pin_new_sneaky_unsafe_pointer;
+
+#[cfg(feature = "alloc")]
+pub mod realloc_sneaky_unsafe_new_size;
+
+pub mod ref_opt_sneaky_unsafe_ptr;
+pub mod ref_sneaky_unsafe_owner;
+
+#[cfg(feature = "std")]
+pub mod remove_var_sneaky_unsafe_key;
+
+pub mod set_sneaky_unsafe_field_index;
+#[cfg(feature = "std")]
+pub mod set_var_sneaky_unsafe_value;
+
+pub mod str_sneaky_unsafe_bytes;
+
+pub mod write_sneaky_unsafe_value;
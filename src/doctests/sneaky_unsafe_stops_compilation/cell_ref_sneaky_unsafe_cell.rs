@@ -0,0 +1,4 @@
+/// ```compile_fail,E0133
+#[doc = include_str!("../../../negative_tests/sneaky_unsafe_stops_compilation/src/bin/cell_ref_sneaky_unsafe_cell.rs")]
+/// ```
+pub const _: () = {};
@@ -0,0 +1,4 @@
+/// ```compile_fail,E0133
+#[doc = include_str!("../../../negative_tests/sneaky_unsafe_stops_compilation/src/bin/pin_new_sneaky_unsafe_pointer.rs")]
+/// ```
+pub const _: () = {};
@@ -0,0 +1,6 @@
+pub mod pin_get_mut_unpin;
+
+pub mod pin_map_unpin_field;
+pub mod pin_map_unpin_target;
+
+pub mod pin_new_unpin;
@@ -0,0 +1,4 @@
+/// ```compile_fail,E0034
+#[doc = include_str!("../../../negative_tests/unpin_stops_compilation/src/bin/pin_map_unpin_field.rs")]
+/// ```
+pub const _: () = {};
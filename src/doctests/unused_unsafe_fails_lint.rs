@@ -1,3 +1,5 @@
+pub mod extern_safe_fn;
+
 pub mod functn_none_args;
 pub mod functn_some_args;
 
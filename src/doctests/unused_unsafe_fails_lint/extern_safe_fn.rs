@@ -0,0 +1,4 @@
+/// ```compile_fail,edition2024
+#[doc = include_str!("../../../negative_tests/unused_unsafe_fails_lint/src/bin/extern_safe_fn.rs")]
+/// ```
+pub const _: () = {};
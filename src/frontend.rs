@@ -0,0 +1,29 @@
+//! Our own `macro_rules!` macros, as opposed to the macros implemented in `prudent-macros-enforce`
+//! (or `prudent-macros-lint`). They are `#[macro_export]`-ed (so that they can be invoked with
+//! `$crate::...` paths), but they are documented and meant to be used through [crate::prelude].
+
+#[cfg(feature = "alloc")]
+mod allocator;
+mod arith;
+mod asm;
+mod asynchronous;
+mod cast;
+mod cell;
+mod container_of;
+mod cstr;
+#[cfg(feature = "std")]
+mod env;
+mod extern_static;
+#[cfg(all(feature = "std", unix))]
+mod fd;
+mod field;
+mod functions;
+mod optional;
+#[cfg(feature = "alloc")]
+mod ownership;
+mod pin;
+mod pointers;
+mod set;
+mod target_feature;
+mod uninit;
+mod utf8;
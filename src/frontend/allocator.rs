@@ -0,0 +1,243 @@
+//! Calling the global allocator. Only with feature `alloc`.
+
+/// Allocate memory with the global allocator. Isolate `unsafe {...}` only for
+/// `alloc::alloc::alloc`. Evaluate the given [core::alloc::Layout] (or the number of items)
+/// **outside** `unsafe {...}`.
+///
+/// Syntax:
+/// - `unsafe_alloc!(layout)` for `layout` being [core::alloc::Layout]. Yield `*mut u8`, which is
+///   null if the allocation failed (like `alloc::alloc::alloc`).
+/// - `unsafe_alloc!(type T)` allocates for one `T`. Yield `*mut T`, which is null if the allocation
+///   failed.
+/// - `unsafe_alloc!(type [T; count])` allocates for `count` items of type `T` (where `count` is any
+///   `usize` expression, evaluated at runtime). Yield `NonNull<[T]>`. If the allocation fails, this
+///   calls `alloc::alloc::handle_alloc_error`. If the layout overflows, this panics. If `count` is
+///   zero, this doesn't allocate, but it yields an empty slice with a dangling (aligned) pointer,
+///   like `Vec::with_capacity(0)`.
+///
+/// The typed forms refuse a zero-sized `T` at compile time. (Allocating zero bytes is undefined
+/// behavior.) In debug builds, the [core::alloc::Layout] form checks (at runtime) that the size is
+/// not zero. For an array type (rather than a slice), use a type alias (like `type Block = [T;
+/// N];`).
+///
+/// Free the memory with [crate::prelude::unsafe_dealloc] (with the same layout, or the same type).
+///
+/// ```
+/// use prudent::prelude::{unsafe_alloc, unsafe_dealloc, unsafe_realloc, unsafe_val, unsafe_write};
+///
+/// fn main() {
+///     let items = unsafe_alloc!(type [u32; 2]);
+///     unsafe_write!(items.cast::<u32>(), 7);
+///
+///     let items = unsafe_realloc!(type [u32], items, 4);
+///     assert_eq!(unsafe_val!(items.cast::<u32>()), 7);
+///     assert_eq!(items.len(), 4);
+///     unsafe_dealloc!(type [u32], items);
+/// }
+/// ```
+///
+/// A zero-sized type is refused:
+/// ```compile_fail,E0080
+/// use prudent::prelude::unsafe_alloc;
+///
+/// fn main() {
+///     let _ = unsafe_alloc!(type ());
+/// }
+/// ```
+///
+/// # Possible violations
+/// The count expression itself is `unsafe`:
+/// ```compile_fail
+#[doc = include_str!("../../negative_tests/sneaky_unsafe_stops_compilation/src/bin/alloc_sneaky_unsafe_count.rs")]
+/// ```
+#[doc(hidden)]
+#[macro_export]
+macro_rules! unsafe_alloc {
+    (@non_zero_sized $type:ty) => {
+        const {
+            ::core::assert!(
+                ::core::mem::size_of::<$type>() != 0,
+                "unsafe_alloc!, unsafe_dealloc! and unsafe_realloc! refuse zero-sized types."
+            )
+        }
+    };
+    (@array_layout $item:ty, $count:ident) => {
+        match ::core::alloc::Layout::array::<$item>($count) {
+            ::core::result::Result::Ok(layout) => layout,
+            ::core::result::Result::Err(_) => ::core::panic!("unsafe_alloc!: capacity overflow."),
+        }
+    };
+    // Yield `NonNull<$item>` for `$count` items, dangling if `$count` is zero.
+    (@slice_ptr $item:ty, $count:ident) => {{
+        let layout = $crate::unsafe_alloc!(@array_layout $item, $count);
+        if layout.size() == 0 {
+            ::core::ptr::NonNull::<$item>::dangling()
+        } else {
+            let ptr = unsafe { $crate::backend::alloc::alloc::alloc(layout) };
+            let ::core::option::Option::Some(ptr) = ::core::ptr::NonNull::new(ptr.cast::<$item>())
+            else {
+                $crate::backend::alloc::alloc::handle_alloc_error(layout)
+            };
+            ptr
+        }
+    }};
+    (@non_zero_size $layout:ident) => {
+        ::core::debug_assert!(
+            $layout.size() != 0,
+            "unsafe_alloc!, unsafe_dealloc! and unsafe_realloc! refuse zero-sized layouts."
+        );
+    };
+    (type [$item:ty; $count:expr] $(,)?) => {{
+        $crate::unsafe_alloc!(@non_zero_sized $item);
+        let count: usize = $count;
+        let ptr = $crate::unsafe_alloc!(@slice_ptr $item, count);
+        ::core::ptr::NonNull::slice_from_raw_parts(ptr, count)
+    }};
+    (type $type:ty $(,)?) => {{
+        $crate::unsafe_alloc!(@non_zero_sized $type);
+        let layout = ::core::alloc::Layout::new::<$type>();
+        unsafe { $crate::backend::alloc::alloc::alloc(layout) }.cast::<$type>()
+    }};
+    ($layout:expr $(,)?) => {{
+        let layout: ::core::alloc::Layout = $layout;
+        $crate::unsafe_alloc!(@non_zero_size layout);
+        unsafe { $crate::backend::alloc::alloc::alloc(layout) }
+    }};
+}
+
+/// Free memory allocated with [crate::prelude::unsafe_alloc] (or otherwise with the global
+/// allocator). Isolate `unsafe {...}` only for `alloc::alloc::dealloc`. Evaluate the given pointer,
+/// and the layout, **outside** `unsafe {...}`.
+///
+/// Syntax (`ptr` may be `*mut` or `NonNull`):
+/// - `unsafe_dealloc!(ptr, layout)`, with any pointee type.
+/// - `unsafe_dealloc!(type T, ptr)` for `ptr` pointing to `T`. The layout is `Layout::new::<T>()`.
+/// - `unsafe_dealloc!(type [T], ptr)` for `ptr` pointing to `[T]` (like the result of
+///   `unsafe_alloc!(type [T; count])`). The layout is that of an array with the slice's length. An
+///   empty slice was not allocated, so it's not freed either.
+///
+/// Like [crate::prelude::unsafe_alloc], the typed forms refuse a zero-sized `T` at compile time.
+///
+/// ```
+/// use core::alloc::Layout;
+/// use prudent::prelude::{unsafe_alloc, unsafe_dealloc};
+///
+/// fn main() {
+///     let layout = Layout::from_size_align(64, 16).unwrap();
+///     let buffer = unsafe_alloc!(layout);
+///     assert!(!buffer.is_null());
+///     unsafe_dealloc!(buffer, layout);
+///
+///     let one = unsafe_alloc!(type u64);
+///     assert!(!one.is_null());
+///     unsafe_dealloc!(type u64, one);
+/// }
+/// ```
+///
+/// # Possible violations
+/// The layout expression itself is `unsafe`:
+/// ```compile_fail
+#[doc = include_str!("../../negative_tests/sneaky_unsafe_stops_compilation/src/bin/dealloc_sneaky_unsafe_layout.rs")]
+/// ```
+#[doc(hidden)]
+#[macro_export]
+macro_rules! unsafe_dealloc {
+    (type [$item:ty], $ptr:expr $(,)?) => {{
+        $crate::unsafe_alloc!(@non_zero_sized $item);
+        let ptr: *mut [$item] = $crate::backend::RawPointer($ptr).into_mut_ptr();
+        let count = ptr.len();
+        let layout = $crate::unsafe_alloc!(@array_layout $item, count);
+        if layout.size() != 0 {
+            unsafe { $crate::backend::alloc::alloc::dealloc(ptr.cast::<u8>(), layout) }
+        }
+    }};
+    (type $type:ty, $ptr:expr $(,)?) => {{
+        $crate::unsafe_alloc!(@non_zero_sized $type);
+        let ptr: *mut $type = $crate::backend::RawPointer($ptr).into_mut_ptr();
+        let layout = ::core::alloc::Layout::new::<$type>();
+        unsafe { $crate::backend::alloc::alloc::dealloc(ptr.cast::<u8>(), layout) }
+    }};
+    ($ptr:expr, $layout:expr $(,)?) => {{
+        let ptr = $crate::backend::RawPointer($ptr).into_mut_ptr().cast::<u8>();
+        let layout: ::core::alloc::Layout = $layout;
+        $crate::unsafe_alloc!(@non_zero_size layout);
+        unsafe { $crate::backend::alloc::alloc::dealloc(ptr, layout) }
+    }};
+}
+
+/// Resize memory allocated with [crate::prelude::unsafe_alloc] (or otherwise with the global
+/// allocator). Isolate `unsafe {...}` only for `alloc::alloc::realloc`. Evaluate the given
+/// pointer, the layout, and the new size (or the new number of items) **outside** `unsafe {...}`.
+///
+/// Syntax (`ptr` may be `*mut` or `NonNull`):
+/// - `unsafe_realloc!(ptr, layout, new_size)`, with any pointee type. Yield `*mut u8`, which is
+///   null if the reallocation failed (like `alloc::alloc::realloc`). Then the old memory is still
+///   allocated.
+/// - `unsafe_realloc!(type [T], ptr, new_count)` for `ptr` pointing to `[T]`. Yield `NonNull<[T]>`
+///   with `new_count` items. If the reallocation fails, this calls
+///   `alloc::alloc::handle_alloc_error`. If the new layout overflows, this panics. Empty slices
+///   are handled like in [crate::prelude::unsafe_alloc]: if `ptr` is empty, this allocates (with
+///   `alloc::alloc::alloc`), and if `new_count` is zero, this frees (with `alloc::alloc::dealloc`)
+///   and yields an empty slice with a dangling pointer.
+///
+/// Like [crate::prelude::unsafe_alloc], the typed form refuses a zero-sized `T` at compile time,
+/// and the untyped form checks (in debug builds) that the new size is not zero.
+///
+/// ```
+/// use core::alloc::Layout;
+/// use prudent::prelude::{unsafe_alloc, unsafe_dealloc, unsafe_realloc};
+///
+/// fn main() {
+///     let layout = Layout::from_size_align(8, 8).unwrap();
+///     let buffer = unsafe_alloc!(layout);
+///     assert!(!buffer.is_null());
+///     let buffer = unsafe_realloc!(buffer, layout, 32);
+///     assert!(!buffer.is_null());
+///     unsafe_dealloc!(buffer, Layout::from_size_align(32, 8).unwrap());
+/// }
+/// ```
+///
+/// # Possible violations
+/// The new size expression itself is `unsafe`:
+/// ```compile_fail
+#[doc = include_str!("../../negative_tests/sneaky_unsafe_stops_compilation/src/bin/realloc_sneaky_unsafe_new_size.rs")]
+/// ```
+#[doc(hidden)]
+#[macro_export]
+macro_rules! unsafe_realloc {
+    (type [$item:ty], $ptr:expr, $new_count:expr $(,)?) => {{
+        $crate::unsafe_alloc!(@non_zero_sized $item);
+        let ptr: *mut [$item] = $crate::backend::RawPointer($ptr).into_mut_ptr();
+        let new_count: usize = $new_count;
+        let count = ptr.len();
+        let layout = $crate::unsafe_alloc!(@array_layout $item, count);
+        let new_layout = $crate::unsafe_alloc!(@array_layout $item, new_count);
+        let new_ptr = if layout.size() == 0 {
+            $crate::unsafe_alloc!(@slice_ptr $item, new_count)
+        } else if new_layout.size() == 0 {
+            unsafe { $crate::backend::alloc::alloc::dealloc(ptr.cast::<u8>(), layout) };
+            ::core::ptr::NonNull::<$item>::dangling()
+        } else {
+            let new_ptr = unsafe {
+                $crate::backend::alloc::alloc::realloc(ptr.cast::<u8>(), layout, new_layout.size())
+            };
+            let ::core::option::Option::Some(new_ptr) =
+                ::core::ptr::NonNull::new(new_ptr.cast::<$item>())
+            else {
+                $crate::backend::alloc::alloc::handle_alloc_error(new_layout)
+            };
+            new_ptr
+        };
+        ::core::ptr::NonNull::slice_from_raw_parts(new_ptr, new_count)
+    }};
+    ($ptr:expr, $layout:expr, $new_size:expr $(,)?) => {{
+        let ptr = $crate::backend::RawPointer($ptr).into_mut_ptr().cast::<u8>();
+        let layout: ::core::alloc::Layout = $layout;
+        let new_size: usize = $new_size;
+        ::core::debug_assert!(
+            new_size != 0,
+            "unsafe_alloc!, unsafe_dealloc! and unsafe_realloc! refuse zero-sized layouts."
+        );
+        unsafe { $crate::backend::alloc::alloc::realloc(ptr, layout, new_size) }
+    }};
+}
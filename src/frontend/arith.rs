@@ -0,0 +1,275 @@
+//! Unchecked integer arithmetic.
+
+/// Evaluate an integer arithmetic expression with "unchecked" operations. Rewrite each binary
+/// operator into the corresponding `unchecked_*` method, and isolate `unsafe {...}` only for those
+/// method invocations. Evaluate all operands **outside** `unsafe {...}`.
+///
+/// | Operator | Method          | Since     |
+/// |----------|-----------------|-----------|
+/// | `+`      | `unchecked_add` | Rust 1.79 |
+/// | `-`      | `unchecked_sub` | Rust 1.79 |
+/// | `*`      | `unchecked_mul` | Rust 1.79 |
+/// | `<<`     | `unchecked_shl` | Rust 1.93 |
+/// | `>>`     | `unchecked_shr` | Rust 1.93 |
+///
+/// Operator precedence and associativity are the same as in Rust: `a * b + c << d` is `((a * b) +
+/// c) << d`, and `a - b - c` is `(a - b) - c`. Parenthesized sub-expressions `(...)` are processed
+/// (rewritten), too. Other (non-operator) parts of operands, like method calls, indexing, `as`
+/// casts or unary `-`, are kept as they are.
+///
+/// In debug builds (with `debug_assertions`), before each unchecked operation this checks (with the
+/// corresponding `checked_*` method) that the operation doesn't overflow. If it would, it panics
+/// with the offending operator and the (sub-)expression.
+///
+/// Generic arguments (like in `Vec::<Vec<u8>>::new()` or `<u8>::MAX`) are a part of their operand,
+/// even if they contain (or end with) `<<` or `>>`.
+///
+/// Because each operand is bound to a local variable before it's used, integer literals need a
+/// type suffix (like `1u8`), unless their type can be inferred from other operands.
+///
+/// Usable in `const` contexts.
+///
+/// ```
+/// use prudent::prelude::unsafe_arith;
+///
+/// const fn mul_add(a: u32, b: u32, c: u32) -> u32 {
+///     unsafe_arith!(a * b + c)
+/// }
+/// const _: u32 = mul_add(2, 3, 4);
+/// const _: u8 = unsafe_arith!((200u8 - 100) * 2 + 5);
+///
+/// fn main() {
+///     let samples = [3i16, -4];
+///     let gain = 2;
+///     let mixed = unsafe_arith!(samples[0] * gain - samples[1] * gain);
+///     assert_eq!(mixed, 14);
+/// }
+/// ```
+///
+/// Not supported (a compile error): `/` and `%` (there are no stable unchecked division methods),
+/// and bitwise binary operators `&`, `|` and `^` (they can't overflow; and they have lower
+/// precedence than the above operators, so use them outside of this macro).
+///
+/// # Possible violations
+/// An operand expression itself is `unsafe`:
+/// ```compile_fail
+#[doc = include_str!("../../negative_tests/sneaky_unsafe_stops_compilation/src/bin/arith_sneaky_unsafe_operand.rs")]
+/// ```
+///
+/// # Implementation notes
+/// This is a "TT muncher". It processes one precedence level at a time: first shifts `<<` and
+/// `>>`, then `+` and `-`, then `*`. On each level it scans the tokens, and it finds the **last**
+/// top-level operator of that level. Tokens inside parenthesis/brackets are one token tree, so
+/// they are skipped by the scan. Tokens before that operator are the left operand (processed on
+/// the same level again), tokens after it are the right operand (processed on the next level).
+/// That gives left associativity.
+///
+/// A `<` (and a `<<` at the start of an operand) opens generic arguments. Then the scan switches
+/// to `@generic`, which appends tokens to the current operand, and which counts the depth of
+/// `<`/`<<` against `>`/`>>` until they match. An unmatched `<` keeps all remaining tokens in the
+/// operand, as they are.
+///
+/// Each level's scan holds `[tokens left of the last operator] [the last operator] [tokens right
+/// of the last operator]`, followed by a flag whether the next token is at the start of an
+/// operand (`yes`) or whether it follows an operand (`no`). Only in the latter case a `-`, `*` or
+/// `&` is a binary operator (rather than an unary one).
+///
+/// Each operand (either the original tokens, or a result of a nested operation) is bound to a
+/// local variable before the operation itself. Every token costs one level of macro recursion, so
+/// very long expressions may need a higher `#![recursion_limit = "..."]`.
+#[doc(hidden)]
+#[macro_export]
+macro_rules! unsafe_arith {
+    // Level: << and >>
+    (@shift $left:tt $op:tt [$($right:tt)+] no & $($rest:tt)*) => {
+        ::core::compile_error!("unsafe_arith! doesn't support `&`.")
+    };
+    (@shift $left:tt $op:tt [$($right:tt)+] no | $($rest:tt)*) => {
+        ::core::compile_error!("unsafe_arith! doesn't support `|`.")
+    };
+    (@shift $left:tt $op:tt [$($right:tt)+] no ^ $($rest:tt)*) => {
+        ::core::compile_error!("unsafe_arith! doesn't support `^`.")
+    };
+    (@shift $left:tt $op:tt [$($right:tt)+] no && $($rest:tt)*) => {
+        ::core::compile_error!("unsafe_arith! doesn't support `&&`.")
+    };
+    (@shift $left:tt $op:tt [$($right:tt)+] no || $($rest:tt)*) => {
+        ::core::compile_error!("unsafe_arith! doesn't support `||`.")
+    };
+    (@shift [] [] [$($right:tt)+] no << $($rest:tt)*) => {
+        $crate::unsafe_arith!(@shift [$($right)+] [<<] [] yes $($rest)*)
+    };
+    (@shift [$($left:tt)+] [$op:tt] [$($right:tt)+] no << $($rest:tt)*) => {
+        $crate::unsafe_arith!(@shift [$($left)+ $op $($right)+] [<<] [] yes $($rest)*)
+    };
+    (@shift [] [] [$($right:tt)+] no >> $($rest:tt)*) => {
+        $crate::unsafe_arith!(@shift [$($right)+] [>>] [] yes $($rest)*)
+    };
+    (@shift [$($left:tt)+] [$op:tt] [$($right:tt)+] no >> $($rest:tt)*) => {
+        $crate::unsafe_arith!(@shift [$($left)+ $op $($right)+] [>>] [] yes $($rest)*)
+    };
+    (@shift [] [] [$($right:tt)+] no) => {
+        $crate::unsafe_arith!(@sum [] [] [] yes $($right)+)
+    };
+    (@shift [$($left:tt)+] [$op:tt] [$($right:tt)+] no) => {
+        $crate::unsafe_arith!(@apply $op [$($left)+] shift [$($right)+] sum)
+    };
+
+    // Level: + and -
+    (@sum [] [] [$($right:tt)+] no + $($rest:tt)*) => {
+        $crate::unsafe_arith!(@sum [$($right)+] [+] [] yes $($rest)*)
+    };
+    (@sum [$($left:tt)+] [$op:tt] [$($right:tt)+] no + $($rest:tt)*) => {
+        $crate::unsafe_arith!(@sum [$($left)+ $op $($right)+] [+] [] yes $($rest)*)
+    };
+    (@sum [] [] [$($right:tt)+] no - $($rest:tt)*) => {
+        $crate::unsafe_arith!(@sum [$($right)+] [-] [] yes $($rest)*)
+    };
+    (@sum [$($left:tt)+] [$op:tt] [$($right:tt)+] no - $($rest:tt)*) => {
+        $crate::unsafe_arith!(@sum [$($left)+ $op $($right)+] [-] [] yes $($rest)*)
+    };
+    (@sum [] [] [$($right:tt)+] no) => {
+        $crate::unsafe_arith!(@product [] [] [] yes $($right)+)
+    };
+    (@sum [$($left:tt)+] [$op:tt] [$($right:tt)+] no) => {
+        $crate::unsafe_arith!(@apply $op [$($left)+] sum [$($right)+] product)
+    };
+
+    // Level: *
+    (@product $left:tt $op:tt [$($right:tt)+] no / $($rest:tt)*) => {
+        ::core::compile_error!("unsafe_arith! doesn't support `/`.")
+    };
+    (@product $left:tt $op:tt [$($right:tt)+] no % $($rest:tt)*) => {
+        ::core::compile_error!("unsafe_arith! doesn't support `%`.")
+    };
+    (@product [] [] [$($right:tt)+] no * $($rest:tt)*) => {
+        $crate::unsafe_arith!(@product [$($right)+] [*] [] yes $($rest)*)
+    };
+    (@product [$($left:tt)+] [$op:tt] [$($right:tt)+] no * $($rest:tt)*) => {
+        $crate::unsafe_arith!(@product [$($left)+ $op $($right)+] [*] [] yes $($rest)*)
+    };
+    (@product [] [] [$($right:tt)+] no) => {
+        $crate::unsafe_arith!(@operand [] [] [] yes $($right)+)
+    };
+    (@product [$($left:tt)+] [$op:tt] [$($right:tt)+] no) => {
+        $crate::unsafe_arith!(@apply $op [$($left)+] product [$($right)+] operand)
+    };
+
+    // Operand: either a parenthesized sub-expression, or anything else.
+    (@operand [] [] [] yes ($($expression:tt)+)) => {
+        $crate::unsafe_arith!($($expression)+)
+    };
+    (@operand [] [] [] yes $($operand:tt)+) => {
+        $($operand)+
+    };
+
+    // Operations.
+    (@apply + $left:tt $left_level:ident $right:tt $right_level:ident) => {
+        $crate::unsafe_arith!(@method + checked_add unchecked_add
+            $left $left_level $right $right_level)
+    };
+    (@apply - $left:tt $left_level:ident $right:tt $right_level:ident) => {
+        $crate::unsafe_arith!(@method - checked_sub unchecked_sub
+            $left $left_level $right $right_level)
+    };
+    (@apply * $left:tt $left_level:ident $right:tt $right_level:ident) => {
+        $crate::unsafe_arith!(@method * checked_mul unchecked_mul
+            $left $left_level $right $right_level)
+    };
+    (@apply << $left:tt $left_level:ident $right:tt $right_level:ident) => {
+        $crate::unsafe_arith!(@method << checked_shl unchecked_shl
+            $left $left_level $right $right_level)
+    };
+    (@apply >> $left:tt $left_level:ident $right:tt $right_level:ident) => {
+        $crate::unsafe_arith!(@method >> checked_shr unchecked_shr
+            $left $left_level $right $right_level)
+    };
+    (@method $op:tt $checked:ident $unchecked:ident
+        [$($left:tt)+] $left_level:ident [$($right:tt)+] $right_level:ident
+    ) => {{
+        let left = $crate::unsafe_arith!(@$left_level [] [] [] yes $($left)+);
+        let right = $crate::unsafe_arith!(@$right_level [] [] [] yes $($right)+);
+        ::core::debug_assert!(
+            left.$checked(right).is_some(),
+            ::core::concat!(
+                "unsafe_arith!: `",
+                ::core::stringify!($op),
+                "` overflows in `",
+                ::core::stringify!($($left)+ $op $($right)+),
+                "`."
+            )
+        );
+        unsafe { left.$unchecked(right) }
+    }};
+
+    // Generic arguments (`::<...>`, `<Type as Trait>::...`, `as Type<...>`): tokens up to the
+    // matching `>` are a part of the current operand, even `<<` and `>>`. The depth is one `x` per
+    // `<` that hasn't been closed yet.
+    (@generic [@$level:ident $left:tt $op:tt [$($right:tt)*]] [$($depth:tt)+] < $($rest:tt)*) => {
+        $crate::unsafe_arith!(@generic [@$level $left $op [$($right)* <]] [$($depth)+ x] $($rest)*)
+    };
+    (@generic [@$level:ident $left:tt $op:tt [$($right:tt)*]] [$($depth:tt)+] << $($rest:tt)*) => {
+        $crate::unsafe_arith!(@generic [@$level $left $op [$($right)* <<]] [$($depth)+ x x] $($rest)*)
+    };
+    (@generic [@$level:ident $left:tt $op:tt [$($right:tt)*]] [x] > $($rest:tt)*) => {
+        $crate::unsafe_arith!(@$level $left $op [$($right)* >] no $($rest)*)
+    };
+    (@generic [@$level:ident $left:tt $op:tt [$($right:tt)*]] [x $($depth:tt)+] > $($rest:tt)*) => {
+        $crate::unsafe_arith!(@generic [@$level $left $op [$($right)* >]] [$($depth)+] $($rest)*)
+    };
+    (@generic [@$level:ident $left:tt $op:tt [$($right:tt)*]] [x $(x)?] >> $($rest:tt)*) => {
+        $crate::unsafe_arith!(@$level $left $op [$($right)* >>] no $($rest)*)
+    };
+    (@generic [@$level:ident $left:tt $op:tt [$($right:tt)*]] [x x $($depth:tt)+] >> $($rest:tt)*) => {
+        $crate::unsafe_arith!(@generic [@$level $left $op [$($right)* >>]] [$($depth)+] $($rest)*)
+    };
+    (@generic [@$level:ident $left:tt $op:tt [$($right:tt)*]] $depth:tt $token:tt $($rest:tt)*) => {
+        $crate::unsafe_arith!(@generic [@$level $left $op [$($right)* $token]] $depth $($rest)*)
+    };
+    // Unmatched `<` (not generic arguments): leave the tokens as they are.
+    (@generic [@$level:ident $left:tt $op:tt [$($right:tt)*]] $depth:tt) => {
+        $crate::unsafe_arith!(@$level $left $op [$($right)*] no)
+    };
+
+    // Flag whether the next token is at the start of an operand.
+    (@flag + [$($state:tt)+] $($rest:tt)*) => { $crate::unsafe_arith!($($state)+ yes $($rest)*) };
+    (@flag - [$($state:tt)+] $($rest:tt)*) => { $crate::unsafe_arith!($($state)+ yes $($rest)*) };
+    (@flag * [$($state:tt)+] $($rest:tt)*) => { $crate::unsafe_arith!($($state)+ yes $($rest)*) };
+    (@flag / [$($state:tt)+] $($rest:tt)*) => { $crate::unsafe_arith!($($state)+ yes $($rest)*) };
+    (@flag % [$($state:tt)+] $($rest:tt)*) => { $crate::unsafe_arith!($($state)+ yes $($rest)*) };
+    (@flag << [$($state:tt)+] $($rest:tt)*) => { $crate::unsafe_arith!($($state)+ yes $($rest)*) };
+    (@flag >> [$($state:tt)+] $($rest:tt)*) => { $crate::unsafe_arith!($($state)+ yes $($rest)*) };
+    (@flag & [$($state:tt)+] $($rest:tt)*) => { $crate::unsafe_arith!($($state)+ yes $($rest)*) };
+    (@flag | [$($state:tt)+] $($rest:tt)*) => { $crate::unsafe_arith!($($state)+ yes $($rest)*) };
+    (@flag ^ [$($state:tt)+] $($rest:tt)*) => { $crate::unsafe_arith!($($state)+ yes $($rest)*) };
+    (@flag ! [$($state:tt)+] $($rest:tt)*) => { $crate::unsafe_arith!($($state)+ yes $($rest)*) };
+    (@flag && [$($state:tt)+] $($rest:tt)*) => { $crate::unsafe_arith!($($state)+ yes $($rest)*) };
+    (@flag || [$($state:tt)+] $($rest:tt)*) => { $crate::unsafe_arith!($($state)+ yes $($rest)*) };
+    (@flag $token:tt [$($state:tt)+] $($rest:tt)*) => {
+        $crate::unsafe_arith!($($state)+ no $($rest)*)
+    };
+
+    // Missing operand (after an operator, or at the very end).
+    (@$level:ident $left:tt $op:tt $right:tt yes) => {
+        ::core::compile_error!("unsafe_arith! is missing an operand at the end.")
+    };
+
+    // Start of generic arguments. (A `<<` that follows an operand is handled by the shift level.)
+    (@$level:ident $left:tt $op:tt [$($right:tt)*] $flag:ident < $($rest:tt)*) => {
+        $crate::unsafe_arith!(@generic [@$level $left $op [$($right)* <]] [x] $($rest)*)
+    };
+    (@$level:ident $left:tt $op:tt [$($right:tt)*] yes << $($rest:tt)*) => {
+        $crate::unsafe_arith!(@generic [@$level $left $op [$($right)* <<]] [x x] $($rest)*)
+    };
+
+    // Any other token (including unary operators) is a part of the current operand. Operators set
+    // the flag to `yes`, so that a following `-`, `*` or `&` is treated as unary, too.
+    (@$level:ident $left:tt $op:tt [$($right:tt)*] $flag:ident $token:tt $($rest:tt)*) => {
+        $crate::unsafe_arith!(@flag $token [@$level $left $op [$($right)* $token]] $($rest)*)
+    };
+
+    // Entry point.
+    ($($expression:tt)+) => {
+        $crate::unsafe_arith!(@shift [] [] [] yes $($expression)+)
+    };
+}
@@ -0,0 +1,211 @@
+//! Inline assembly.
+
+/// Like [core::arch::asm], but isolate `unsafe {...}` only for the `asm!(...)` itself. Evaluate
+/// (expressions of) input operands **before**, and assign output operands to their places
+/// **after**, the `unsafe {...}` block.
+///
+/// Same syntax as [core::arch::asm]: one or more template strings, followed by operands,
+/// `clobber_abi(...)` and `options(...)`. Supported operands (each one optionally named with `name
+/// = ...`):
+/// - `in(reg) expr`: `expr` is evaluated and bound to a local variable before `unsafe {...}`.
+/// - `out(reg) place` and `lateout(reg) place`: the result is assigned to `place` after `unsafe
+///   {...}`. `place` may be `_`.
+/// - `inout(reg) place`: `place` is read before, and assigned to after, `unsafe {...}`. Hence
+///   `place` is evaluated twice - keep it simple (for example, a local variable).
+/// - `inout(reg) expr => place` and `inlateout(reg) expr => place`: `expr` is evaluated before, and
+///   the result is assigned to `place` after, `unsafe {...}`. `place` may be `_`.
+/// - `const expr` and `sym path`: passed to [core::arch::asm] as they are (they are evaluated at
+///   compile time). `const expr` is verified to be safe in an inactive `if false {...}` branch.
+///
+/// The register (class) may be given either as an identifier (like `reg`), or as a string literal
+/// with an explicit register (like `"eax"`).
+///
+/// Because places are assigned outside `unsafe {...}`, a place that needs `unsafe` (like `*ptr`
+/// for a raw pointer `ptr`, or a `static mut`) doesn't compile. Use a local variable instead, and
+/// then [crate::prelude::unsafe_set] or [crate::prelude::unsafe_static_set].
+///
+/// ```
+/// # // Miri can't run inline assembly.
+/// # #[cfg(all(target_arch = "x86_64", not(miri)))]
+/// # mod x86_64 {
+/// use prudent::prelude::unsafe_asm;
+///
+/// pub fn main() {
+///     let values = [2u64, 3];
+///     let sum: u64;
+///     let mut product = values[0];
+///     unsafe_asm!(
+///         "mov {sum}, {a}",
+///         "add {sum}, {b}",
+///         "imul {product}, {b}",
+///         a = in(reg) values[0],
+///         b = in(reg) values[1],
+///         sum = out(reg) sum,
+///         product = inout(reg) product,
+///         options(pure, nomem, nostack),
+///     );
+///     assert_eq!(sum, 5);
+///     assert_eq!(product, 6);
+/// }
+/// # }
+/// # fn main() {
+/// #     #[cfg(all(target_arch = "x86_64", not(miri)))]
+/// #     x86_64::main();
+/// # }
+/// ```
+///
+/// # Possible violations
+/// An input operand expression itself is `unsafe`:
+/// ```compile_fail
+#[doc = include_str!("../../negative_tests/sneaky_unsafe_stops_compilation/src/bin/asm_sneaky_unsafe_in_operand.rs")]
+/// ```
+///
+/// An output operand place itself needs `unsafe`:
+/// ```compile_fail
+#[doc = include_str!("../../negative_tests/sneaky_unsafe_stops_compilation/src/bin/asm_sneaky_unsafe_out_place.rs")]
+/// ```
+///
+/// # Implementation notes
+/// This is a "TT muncher". It first collects the template string(s), then it processes operands one
+/// by one. For each input it generates a nested block with a `let` binding. For each output it
+/// generates an (uninitialized) `let` binding, and it collects an assignment to the original place.
+/// The local variables are hygienic, so they don't conflict with the user's variables (nor with
+/// each other). Finally, inside all those nested blocks, it generates `unsafe { asm!(...) }`
+/// (referring to the local variables), followed by the collected assignments (if any).
+#[doc(hidden)]
+#[macro_export]
+macro_rules! unsafe_asm {
+    // Template string(s).
+    (@template [$($templates:tt)*] $template:literal, $($rest:tt)*) => {
+        $crate::unsafe_asm!(@template [$($templates)* $template,] $($rest)*)
+    };
+    (@template [$($templates:tt)+] $($rest:tt)*) => {
+        $crate::unsafe_asm!(@operand [$($templates)+] [] [] $($rest)*)
+    };
+
+    // All operands processed.
+    (@operand [$($templates:tt)+] [$($operands:tt)*] [] $(,)*) => {
+        unsafe { ::core::arch::asm!($($templates)+ $($operands)*) }
+    };
+    (@operand [$($templates:tt)+] [$($operands:tt)*] [$($assignments:tt)+] $(,)*) => {{
+        unsafe { ::core::arch::asm!($($templates)+ $($operands)*) };
+        $($assignments)+
+    }};
+
+    // Not operands.
+    (@operand $templates:tt [$($operands:tt)*] $assignments:tt
+        clobber_abi($($abi:tt)*), $($rest:tt)*
+    ) => {
+        $crate::unsafe_asm!(@operand $templates [$($operands)* clobber_abi($($abi)*),]
+            $assignments $($rest)*)
+    };
+    (@operand $templates:tt [$($operands:tt)*] $assignments:tt
+        options($($option:tt)*), $($rest:tt)*
+    ) => {
+        $crate::unsafe_asm!(@operand $templates [$($operands)* options($($option)*),]
+            $assignments $($rest)*)
+    };
+
+    // Operands, with or without a name.
+    (@operand $templates:tt $operands:tt $assignments:tt $name:ident = $($rest:tt)*) => {
+        $crate::unsafe_asm!(@op $templates $operands $assignments [$name =] $($rest)*)
+    };
+    (@operand $templates:tt $operands:tt $assignments:tt $($rest:tt)+) => {
+        $crate::unsafe_asm!(@op $templates $operands $assignments [] $($rest)+)
+    };
+    // Inputs.
+    (@op $templates:tt [$($operands:tt)*] $assignments:tt
+        [$($name:tt)*] in($reg:tt) $input:expr, $($rest:tt)*
+    ) => {{
+        let input = $input;
+        $crate::unsafe_asm!(@operand $templates [$($operands)* $($name)* in($reg) input,]
+            $assignments $($rest)*)
+    }};
+
+    // Outputs.
+    (@op $templates:tt [$($operands:tt)*] $assignments:tt
+        [$($name:tt)*] $kind:ident($reg:tt) _, $($rest:tt)*
+    ) => {
+        $crate::unsafe_asm!(@operand $templates [$($operands)* $($name)* $kind($reg) _,]
+            $assignments $($rest)*)
+    };
+    (@op $templates:tt [$($operands:tt)*] [$($assignments:tt)*]
+        [$($name:tt)*] out($reg:tt) $place:expr, $($rest:tt)*
+    ) => {{
+        let output;
+        $crate::unsafe_asm!(@operand $templates [$($operands)* $($name)* out($reg) output,]
+            [$($assignments)* $place = output;] $($rest)*)
+    }};
+    (@op $templates:tt [$($operands:tt)*] [$($assignments:tt)*]
+        [$($name:tt)*] lateout($reg:tt) $place:expr, $($rest:tt)*
+    ) => {{
+        let output;
+        $crate::unsafe_asm!(@operand $templates [$($operands)* $($name)* lateout($reg) output,]
+            [$($assignments)* $place = output;] $($rest)*)
+    }};
+
+    // Inputs & outputs.
+    (@op $templates:tt [$($operands:tt)*] $assignments:tt
+        [$($name:tt)*] $kind:ident($reg:tt) $input:expr => _, $($rest:tt)*
+    ) => {{
+        let input = $input;
+        $crate::unsafe_asm!(@operand $templates [$($operands)* $($name)* $kind($reg) input => _,]
+            $assignments $($rest)*)
+    }};
+    (@op $templates:tt [$($operands:tt)*] [$($assignments:tt)*]
+        [$($name:tt)*] $kind:ident($reg:tt) $input:expr => $place:expr, $($rest:tt)*
+    ) => {{
+        let input = $input;
+        let output;
+        $crate::unsafe_asm!(@operand $templates
+            [$($operands)* $($name)* $kind($reg) input => output,]
+            [$($assignments)* $place = output;] $($rest)*)
+    }};
+    (@op $templates:tt [$($operands:tt)*] [$($assignments:tt)*]
+        [$($name:tt)*] inout($reg:tt) $place:expr, $($rest:tt)*
+    ) => {{
+        let mut in_out = $place;
+        $crate::unsafe_asm!(@operand $templates [$($operands)* $($name)* inout($reg) in_out,]
+            [$($assignments)* $place = in_out;] $($rest)*)
+    }};
+    (@op $templates:tt [$($operands:tt)*] [$($assignments:tt)*]
+        [$($name:tt)*] inlateout($reg:tt) $place:expr, $($rest:tt)*
+    ) => {{
+        let mut in_out = $place;
+        $crate::unsafe_asm!(@operand $templates [$($operands)* $($name)* inlateout($reg) in_out,]
+            [$($assignments)* $place = in_out;] $($rest)*)
+    }};
+
+    // Compile time operands.
+    (@op $templates:tt [$($operands:tt)*] $assignments:tt
+        [$($name:tt)*] const $constant:expr, $($rest:tt)*
+    ) => {{
+        if false {
+            let _ = $constant;
+        }
+        $crate::unsafe_asm!(@operand $templates [$($operands)* $($name)* const $constant,]
+            $assignments $($rest)*)
+    }};
+    (@op $templates:tt [$($operands:tt)*] $assignments:tt
+        [$($name:tt)*] sym $symbol:path, $($rest:tt)*
+    ) => {
+        $crate::unsafe_asm!(@operand $templates [$($operands)* $($name)* sym $symbol,]
+            $assignments $($rest)*)
+    };
+
+    // Errors.
+    (@op $templates:tt $operands:tt $assignments:tt $name:tt $($rest:tt)*) => {
+        ::core::compile_error!(::core::concat!(
+            "unsafe_asm! doesn't support (or can't parse) operand: ",
+            ::core::stringify!($($rest)*)
+        ))
+    };
+    (@template [] $($rest:tt)*) => {
+        ::core::compile_error!("unsafe_asm! expects template string(s) first.")
+    };
+
+    // Entry point. Append a comma, so that each operand above can expect one.
+    ($($tokens:tt)+) => {
+        $crate::unsafe_asm!(@template [] $($tokens)+ ,)
+    };
+}
@@ -0,0 +1,110 @@
+//! Invoking `unsafe` `async` functions and methods.
+
+/// Invoke an `async unsafe` function, but isolate `unsafe {...}` only for **creating** the
+/// future. Yield that future, so that it's awaited (`.await`) **outside** `unsafe {...}`.
+///
+/// Same syntax and same checks as [crate::prelude::unsafe_fn]. On top of that, this ensures that
+/// the function returns a [core::future::Future].
+///
+/// Why? Calling an `async unsafe` function needs `unsafe {...}` only to create the future. But
+/// the usual `unsafe { f(x).await }` pulls the whole await point into the `unsafe {...}` block.
+/// Instead, write `unsafe_fn_async!(f; x).await`.
+///
+/// ```
+/// use prudent::prelude::unsafe_fn_async;
+///
+/// /// # Safety
+/// /// `bytes` must not be empty.
+/// async unsafe fn first(bytes: &[u8]) -> u8 {
+///     unsafe { *bytes.get_unchecked(0) }
+/// }
+///
+/// async fn first_or_zero(bytes: &[u8]) -> u8 {
+///     if bytes.is_empty() {
+///         0
+///     } else {
+///         unsafe_fn_async!(first; bytes).await
+///     }
+/// }
+///
+/// fn main() {
+///     let _future = first_or_zero(&[1, 2]);
+/// }
+/// ```
+///
+/// # Possible violations
+/// A passed parameter itself is `unsafe`. Even though the (future's) result is awaited outside
+/// `unsafe {...}`, the arguments are not evaluated there.
+#[cfg_attr(not(feature = "lint_unused_unsafe"), doc = " ```compile_fail")]
+#[cfg_attr(feature = "lint_unused_unsafe", doc = " ```ignore")]
+#[doc = include_str!("../../negative_tests/sneaky_unsafe_stops_compilation/src/bin/functn_async_sneaky_unsafe_arg.rs")]
+/// ```
+///
+/// The function is `unsafe`, but not `async`:
+/// ```compile_fail
+/// use prudent::prelude::unsafe_fn_async;
+/// unsafe fn not_async() {}
+///
+/// async fn caller() {
+///     unsafe_fn_async!(not_async).await
+/// }
+/// fn main() {}
+/// ```
+///
+/// # Implementation notes
+/// This forwards all input to [crate::prelude::unsafe_fn], and it passes the result through
+/// [crate::backend::expect_future].
+#[doc(hidden)]
+#[macro_export]
+macro_rules! unsafe_fn_async {
+    ($($tokens:tt)+) => {
+        $crate::backend::expect_future($crate::unsafe_fn!($($tokens)+))
+    };
+}
+
+/// Invoke an `async unsafe` method, but isolate `unsafe {...}` only for **creating** the future.
+/// Yield that future, so that it's awaited (`.await`) **outside** `unsafe {...}`.
+///
+/// Like [crate::prelude::unsafe_fn_async], but for methods. Same syntax and same checks as
+/// [crate::prelude::unsafe_method].
+///
+/// ```
+/// use prudent::prelude::unsafe_method_async;
+///
+/// struct Buffer {
+///     bytes: [u8; 4],
+/// }
+/// impl Buffer {
+///     /// # Safety
+///     /// `index` must be less than 4.
+///     async unsafe fn at(&self, index: usize) -> u8 {
+///         unsafe { *self.bytes.get_unchecked(index) }
+///     }
+/// }
+///
+/// async fn last(buffer: &Buffer) -> u8 {
+///     unsafe_method_async!(buffer =>. at; 3).await
+/// }
+///
+/// fn main() {
+///     let buffer = Buffer { bytes: [1, 2, 3, 4] };
+///     let _future = last(&buffer);
+/// }
+/// ```
+///
+/// # Possible violations
+#[cfg_attr(not(feature = "lint_unused_unsafe"), doc = " ```compile_fail")]
+#[cfg_attr(feature = "lint_unused_unsafe", doc = " ```ignore")]
+#[doc = include_str!("../../negative_tests/sneaky_unsafe_stops_compilation/src/bin/method_async_sneaky_unsafe_self.rs")]
+/// ```
+///
+/// # Implementation notes
+/// This forwards all input to [crate::prelude::unsafe_method], and it passes the result through
+/// [crate::backend::expect_future].
+#[doc(hidden)]
+#[macro_export]
+macro_rules! unsafe_method_async {
+    ($($tokens:tt)+) => {
+        $crate::backend::expect_future($crate::backend::prudent_macros::unsafe_method!($($tokens)+))
+    };
+}
@@ -0,0 +1,195 @@
+//! Reinterpreting pointers (casting them to a different pointee type), and dereferencing them.
+
+/// Cast a pointer to a different pointee type, and yield a reference. Isolate `unsafe {...}` only
+/// for the dereference (done by [crate::prelude::unsafe_ref] or [crate::prelude::unsafe_mut]).
+/// Evaluate the given pointer **outside** `unsafe {...}`.
+///
+/// This is the only `prudent` macro that reinterprets memory. Both the source and the target types
+/// are written out, so reviewers can see the reinterpretation.
+///
+/// Syntax (`pt` is `*const From`, `*mut From` or `NonNull<From>`; with `mut` it's `*mut From` or
+/// `NonNull<From>`):
+/// - `unsafe_cast!(pt, From => To)` yields `&To`, and `unsafe_cast!(mut pt, From => To)` yields
+///   `&mut To`. Both `From` and `To` must be [Sized].
+/// - `unsafe_cast!(pt, [From] => To)` (and with `mut`) yields `&To` (or `&mut To`) from the start
+///   of a slice. `To` must be [Sized] (it may be an array `[To; N]`). In debug builds, it checks
+///   that the slice (in bytes) is not smaller than `To`.
+/// - `unsafe_cast!(pt, [From] => [To])` yields `&[To]` with the same length (number of items) as
+///   the given slice. It checks at compile time that `From` and `To` have the same size. (Hence it
+///   refuses, for example, `[u8] => [u32]`.)
+/// - `unsafe_cast!(pt, [From] => [To; _])` yields `&[To]` with the length recomputed, so that it
+///   covers the same number of bytes. In debug builds, it checks that the number of bytes is a
+///   multiple of the size of `To`. It refuses (at compile time) a zero-sized `To`.
+/// - Similarly for slices with `mut`: `unsafe_cast!(mut pt, [From] => [To])` and
+///   `unsafe_cast!(mut pt, [From] => [To; _])`.
+///
+/// `From` must be the pointee type of `pt` (or `pt` must unsize-coerce to `*const From`, like for
+/// [crate::prelude::unsafe_ref]'s typed form).
+///
+/// In debug builds, this checks (at runtime) that the pointer is aligned for `To`. If not, it
+/// panics. Because of that check, this is not usable in `const` contexts.
+///
+/// ```
+/// use prudent::prelude::unsafe_cast;
+///
+/// #[repr(C)]
+/// struct Header {
+///     magic: u32,
+///     len: u32,
+/// }
+///
+/// #[repr(C, align(4))]
+/// struct Packet([u8; 12]);
+///
+/// fn main() {
+///     let mut packet = Packet([1, 0, 0, 0, 4, 0, 0, 0, 0xAA, 0xBB, 0xCC, 0xDD]);
+///     let bytes: *const [u8] = &packet.0[..];
+///
+///     let header: &Header = unsafe_cast!(bytes.cast::<u8>(), u8 => Header);
+///     assert_eq!((header.magic, header.len), (u32::from_le(1), u32::from_le(4)));
+///
+///     let words: &[u32] = unsafe_cast!(bytes, [u8] => [u32; _]);
+///     assert_eq!(words.len(), 3);
+///
+///     let bytes: *mut [u8] = &mut packet.0[..];
+///     let signed: &mut [i8] = unsafe_cast!(mut bytes, [u8] => [i8]);
+///     signed[8] = -1;
+///     assert_eq!(packet.0[8], 0xFF);
+/// }
+/// ```
+///
+/// A slice cast changes the item size, but it doesn't recompute the length:
+/// ```compile_fail,E0080
+/// use prudent::prelude::unsafe_cast;
+///
+/// fn main() {
+///     let words = [0u32; 2];
+///     let bytes: *const [u8] = core::ptr::slice_from_raw_parts(words.as_ptr().cast::<u8>(), 8);
+///     let _ = unsafe_cast!(bytes, [u8] => [u32]);
+/// }
+/// ```
+///
+/// `From` is unsized (other than a slice), so its size can't be checked:
+/// ```compile_fail,E0277
+/// use prudent::prelude::unsafe_cast;
+///
+/// fn main() {
+///     let text: *const str = "abcd";
+///     let _ = unsafe_cast!(text, str => u32);
+/// }
+/// ```
+///
+/// `From` is not the pointee type:
+/// ```compile_fail,E0308
+/// use prudent::prelude::unsafe_cast;
+///
+/// fn main() {
+///     let value = 1u16;
+///     let pt: *const u16 = &value;
+///     let _ = unsafe_cast!(pt, u8 => i16);
+/// }
+/// ```
+///
+/// # Possible violations
+/// The pointer expression itself is `unsafe`:
+/// ```compile_fail
+#[doc = include_str!("../../negative_tests/sneaky_unsafe_stops_compilation/src/bin/cast_sneaky_unsafe_ptr.rs")]
+/// ```
+///
+/// # Implementation notes
+/// `[From] => [To; N]` doesn't match the `[From] => [To; _]` arm (nor the `[From] => [To]` arm), so
+/// it's handled by the `[From] => To` arm, which checks the size.
+///
+/// The arms for `*const` and for `*mut` are the same, except for the pointer kind (`const` or
+/// `mut`), the conversion method of [crate::backend::RawPointer], the function that creates slice
+/// pointers, and the macro that dereferences. So they're passed to the internal `@cast` arms.
+#[doc(hidden)]
+#[macro_export]
+macro_rules! unsafe_cast {
+    (@aligned $ptr:ident, $to:ty) => {
+        ::core::debug_assert!(
+            $ptr.cast::<$to>().is_aligned(),
+            ::core::concat!(
+                "unsafe_cast!: the pointer is not aligned for `",
+                ::core::stringify!($to),
+                "`."
+            )
+        );
+    };
+    (@cast [$kind:tt $into:ident $slice_from_raw_parts:ident $deref:ident]
+        $ptr:expr, [$from:ty] => [$to:ty; _]
+    ) => {{
+        let ptr: *$kind [$from] = $crate::backend::RawPointer($ptr).$into();
+        const {
+            ::core::assert!(
+                ::core::mem::size_of::<$to>() != 0,
+                "unsafe_cast! can't recompute the length for a zero-sized type."
+            )
+        };
+        let bytes = ptr.len() * ::core::mem::size_of::<$from>();
+        ::core::debug_assert!(
+            bytes % ::core::mem::size_of::<$to>() == 0,
+            ::core::concat!(
+                "unsafe_cast!: the slice size (in bytes) is not a multiple of the size of `",
+                ::core::stringify!($to),
+                "`."
+            )
+        );
+        $crate::unsafe_cast!(@aligned ptr, $to);
+        let ptr = ::core::ptr::$slice_from_raw_parts(
+            ptr.cast::<$to>(),
+            bytes / ::core::mem::size_of::<$to>(),
+        );
+        $crate::$deref!(ptr)
+    }};
+    (@cast [$kind:tt $into:ident $slice_from_raw_parts:ident $deref:ident]
+        $ptr:expr, [$from:ty] => [$to:ty]
+    ) => {{
+        let ptr: *$kind [$from] = $crate::backend::RawPointer($ptr).$into();
+        const {
+            ::core::assert!(
+                ::core::mem::size_of::<$from>() == ::core::mem::size_of::<$to>(),
+                "unsafe_cast! refuses to change the item size of a slice. Use `[To; _]` to \
+                 recompute the length."
+            )
+        };
+        $crate::unsafe_cast!(@aligned ptr, $to);
+        let ptr = ::core::ptr::$slice_from_raw_parts(ptr.cast::<$to>(), ptr.len());
+        $crate::$deref!(ptr)
+    }};
+    (@cast [$kind:tt $into:ident $slice_from_raw_parts:ident $deref:ident]
+        $ptr:expr, [$from:ty] => $to:ty
+    ) => {{
+        let ptr: *$kind [$from] = $crate::backend::RawPointer($ptr).$into();
+        ::core::debug_assert!(
+            ptr.len() * ::core::mem::size_of::<$from>() >= ::core::mem::size_of::<$to>(),
+            ::core::concat!(
+                "unsafe_cast!: the slice size (in bytes) is smaller than the size of `",
+                ::core::stringify!($to),
+                "`."
+            )
+        );
+        $crate::unsafe_cast!(@aligned ptr, $to);
+        let ptr = ptr.cast::<$to>();
+        $crate::$deref!(ptr)
+    }};
+    (@cast [$kind:tt $into:ident $slice_from_raw_parts:ident $deref:ident]
+        $ptr:expr, $from:ty => $to:ty
+    ) => {{
+        let ptr: *$kind $from = $crate::backend::RawPointer($ptr).$into();
+        // Refuse an unsized `From` (like `str` or `dyn Trait`): there's no size to check.
+        let _: fn() -> usize = ::core::mem::size_of::<$from>;
+        $crate::unsafe_cast!(@aligned ptr, $to);
+        let ptr = ptr.cast::<$to>();
+        $crate::$deref!(ptr)
+    }};
+
+    (mut $ptr:expr, $($types:tt)+) => {
+        $crate::unsafe_cast!(@cast [mut into_mut_ptr slice_from_raw_parts_mut unsafe_mut]
+            $ptr, $($types)+)
+    };
+    ($ptr:expr, $($types:tt)+) => {
+        $crate::unsafe_cast!(@cast [const into_const_ptr slice_from_raw_parts unsafe_ref]
+            $ptr, $($types)+)
+    };
+}
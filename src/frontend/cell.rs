@@ -0,0 +1,170 @@
+//! References to the content of an [UnsafeCell](core::cell::UnsafeCell) (or a
+//! [Cell](core::cell::Cell)).
+
+/// Get a shared reference to the content of an [UnsafeCell](core::cell::UnsafeCell) (or of a
+/// [Cell](core::cell::Cell)). Isolate `unsafe {...}` only for dereferencing the pointer from
+/// [UnsafeCell::get](core::cell::UnsafeCell::get) (or [Cell::as_ptr](core::cell::Cell::as_ptr)).
+/// Evaluate the given cell reference, and get the pointer, **outside** `unsafe {...}`.
+///
+/// Syntax:
+/// - `unsafe_cell_ref!(cell)` for `cell` being `&UnsafeCell<T>` or `&Cell<T>`. Yield `&T`, with
+///   the same lifetime as `cell` (not an unbounded one).
+/// - `unsafe_cell_ref!(cell, 'a)` for `cell` being `&'a UnsafeCell<T>` or `&'a Cell<T>`. Yield
+///   `&'a T`.
+///
+/// `T` may be unsized. Usable in `const fn`.
+///
+/// A spin lock, where the guard gives access to the locked data:
+/// ```
+/// use core::cell::UnsafeCell;
+/// use core::hint;
+/// use core::ops::{Deref, DerefMut};
+/// use core::sync::atomic::{AtomicBool, Ordering};
+/// use prudent::prelude::{unsafe_cell_mut, unsafe_cell_ref};
+///
+/// pub struct SpinLock<T> {
+///     locked: AtomicBool,
+///     data: UnsafeCell<T>,
+/// }
+/// // SAFETY: `data` is accessed only through a `SpinGuard`, and there is at most one at a time.
+/// unsafe impl<T: Send> Sync for SpinLock<T> {}
+///
+/// impl<T> SpinLock<T> {
+///     pub const fn new(data: T) -> Self {
+///         Self { locked: AtomicBool::new(false), data: UnsafeCell::new(data) }
+///     }
+///     pub fn lock(&self) -> SpinGuard<'_, T> {
+///         while self
+///             .locked
+///             .compare_exchange_weak(false, true, Ordering::Acquire, Ordering::Relaxed)
+///             .is_err()
+///         {
+///             hint::spin_loop();
+///         }
+///         SpinGuard { lock: self }
+///     }
+/// }
+///
+/// pub struct SpinGuard<'a, T> {
+///     lock: &'a SpinLock<T>,
+/// }
+/// impl<T> Deref for SpinGuard<'_, T> {
+///     type Target = T;
+///     fn deref(&self) -> &T {
+///         // The guard holds the lock, so nothing writes to the data now.
+///         unsafe_cell_ref!(&self.lock.data)
+///     }
+/// }
+/// impl<T> DerefMut for SpinGuard<'_, T> {
+///     fn deref_mut(&mut self) -> &mut T {
+///         // The guard holds the lock, and `&mut self` is exclusive.
+///         unsafe_cell_mut!(&self.lock.data)
+///     }
+/// }
+/// impl<T> Drop for SpinGuard<'_, T> {
+///     fn drop(&mut self) {
+///         self.lock.locked.store(false, Ordering::Release);
+///     }
+/// }
+///
+/// static COUNTER: SpinLock<u32> = SpinLock::new(0);
+///
+/// fn main() {
+///     *COUNTER.lock() += 1;
+///     let guard = COUNTER.lock();
+///     assert_eq!(*guard, 1);
+/// }
+/// ```
+///
+/// # Possible violations
+/// The cell expression itself is `unsafe`:
+/// ```compile_fail
+#[doc = include_str!("../../negative_tests/sneaky_unsafe_stops_compilation/src/bin/cell_ref_sneaky_unsafe_cell.rs")]
+/// ```
+///
+/// # Implementation notes
+/// The pointer comes from [crate::backend::CellReference], whose conversions are inherent `const
+/// fn`s (one for `UnsafeCell`, one for `Cell`). The lifetime of the result is tied to the cell
+/// reference by [crate::backend::borrowing_ref].
+#[doc(hidden)]
+#[macro_export]
+macro_rules! unsafe_cell_ref {
+    ($cell:expr, $lifetime:lifetime $(,)?) => {{
+        let cell: &$lifetime _ = $cell;
+        let reference: &$lifetime _ = $crate::unsafe_cell_ref!(cell);
+        reference
+    }};
+    ($cell:expr $(,)?) => {{
+        let cell = $cell;
+        let ptr = $crate::backend::CellReference(cell).into_mut_ptr();
+        $crate::backend::borrowing_ref(cell, unsafe { &*ptr })
+    }};
+}
+
+/// Get a mutable reference to the content of an [UnsafeCell](core::cell::UnsafeCell) (or of a
+/// [Cell](core::cell::Cell)), through a shared reference to the cell. Isolate `unsafe {...}` only
+/// for dereferencing the pointer from [UnsafeCell::get](core::cell::UnsafeCell::get) (or
+/// [Cell::as_ptr](core::cell::Cell::as_ptr)). Evaluate the given cell reference, and get the
+/// pointer, **outside** `unsafe {...}`.
+///
+/// Syntax:
+/// - `unsafe_cell_mut!(cell)` for `cell` being `&UnsafeCell<T>` or `&Cell<T>`. Yield `&mut T`,
+///   with the same lifetime as `cell` (not an unbounded one).
+/// - `unsafe_cell_mut!(cell, 'a)` for `cell` being `&'a UnsafeCell<T>` or `&'a Cell<T>`. Yield
+///   `&'a mut T`.
+///
+/// `T` may be unsized.
+///
+/// For a spin lock guard, see [crate::prelude::unsafe_cell_ref]. A guard that can be turned into
+/// a reference that lives as long as the lock (and that keeps it locked forever):
+/// ```
+/// use core::cell::UnsafeCell;
+/// use core::sync::atomic::{AtomicBool, Ordering};
+/// use prudent::prelude::unsafe_cell_mut;
+///
+/// pub struct OnceLock<T> {
+///     taken: AtomicBool,
+///     data: UnsafeCell<T>,
+/// }
+/// impl<T> OnceLock<T> {
+///     /// Return the data only the first time.
+///     pub fn take<'a>(&'a self) -> Option<&'a mut T> {
+///         if self.taken.swap(true, Ordering::Acquire) {
+///             None
+///         } else {
+///             Some(unsafe_cell_mut!(&self.data, 'a))
+///         }
+///     }
+/// }
+///
+/// fn main() {
+///     let lock = OnceLock { taken: AtomicBool::new(false), data: UnsafeCell::new(vec![1]) };
+///     lock.take().unwrap().push(2);
+///     assert!(lock.take().is_none());
+///     assert_eq!(lock.data.into_inner(), [1, 2]);
+/// }
+/// ```
+///
+/// # Possible violations
+/// The cell expression itself is `unsafe`:
+/// ```compile_fail
+#[doc = include_str!("../../negative_tests/sneaky_unsafe_stops_compilation/src/bin/cell_mut_sneaky_unsafe_cell.rs")]
+/// ```
+///
+/// # Implementation notes
+/// Like [crate::prelude::unsafe_cell_ref], but the lifetime of the result is tied to the cell
+/// reference by [crate::backend::borrowing_cell_mut] (which is not `const`).
+#[doc(hidden)]
+#[macro_export]
+macro_rules! unsafe_cell_mut {
+    ($cell:expr, $lifetime:lifetime $(,)?) => {{
+        let cell: &$lifetime _ = $cell;
+        let reference: &$lifetime mut _ = $crate::unsafe_cell_mut!(cell);
+        reference
+    }};
+    ($cell:expr $(,)?) => {{
+        let cell = $cell;
+        let ptr = $crate::backend::CellReference(cell).into_mut_ptr();
+        $crate::backend::borrowing_cell_mut(cell, unsafe { &mut *ptr })
+    }};
+}
@@ -0,0 +1,111 @@
+//! Getting a pointer (or a reference) to a container struct from a pointer to its field.
+
+/// Given a raw pointer to a field, yield a raw pointer (or a reference) to the enclosing struct
+/// (the "container"). This is `container_of` from intrusive data structures (and from the Linux
+/// kernel). Isolate `unsafe {...}` only for the pointer arithmetic (and for the dereference, if
+/// yielding a reference). Evaluate the given field pointer **outside** `unsafe {...}`.
+///
+/// Syntax (`field` may be a nested path like `link.next`, or a tuple index like `0`):
+/// - `unsafe_container_of!(ptr, Container, field)` for `ptr` being `*const F`, `*mut F` or
+///   `NonNull<F>`. Yield `*const Container`.
+/// - `unsafe_container_of!(mut ptr, Container, field)` for `ptr` being `*mut F` or `NonNull<F>`.
+///   Yield `*mut Container`.
+/// - `unsafe_container_of!(ref ptr, Container, field)` yields `&Container`, and
+///   `unsafe_container_of!(ref mut ptr, Container, field)` yields `&mut Container`. These pass the
+///   container pointer to [crate::prelude::unsafe_ref] or [crate::prelude::unsafe_mut]. Like there,
+///   a lifetime may be given at the end, like `unsafe_container_of!(ref ptr, Container, field,
+///   'a)`.
+///
+/// The offset is determined with [core::mem::offset_of] (nested field paths need Rust 1.82+). This
+/// checks at compile time that the
+/// type of `field` (in `Container`) is the same as the pointee type of `ptr`.
+///
+/// The field pointer must be allowed to access the whole container (not just the field). So derive
+/// it from a pointer to the container (for example, with [crate::prelude::unsafe_field]), rather
+/// than from a reference to the field.
+///
+/// Usable in `const` contexts.
+///
+/// ```
+/// use prudent::prelude::{unsafe_container_of, unsafe_field};
+///
+/// struct Link {
+///     next: *const Link,
+/// }
+/// struct Task {
+///     id: u32,
+///     link: Link,
+/// }
+///
+/// fn main() {
+///     let task = Task { id: 7, link: Link { next: core::ptr::null() } };
+///     // An intrusive list passes around only pointers to `Link`s:
+///     let link: *const Link = unsafe_field!(&task as *const Task => .link);
+///
+///     let task_ptr: *const Task = unsafe_container_of!(link, Task, link);
+///     assert_eq!(task_ptr, &task as *const Task);
+///     let task_ref: &Task = unsafe_container_of!(ref link, Task, link);
+///     assert_eq!(task_ref.id, 7);
+///     assert!(task_ref.link.next.is_null());
+/// }
+/// ```
+///
+/// The field type doesn't match the pointee:
+/// ```compile_fail,E0308
+/// use prudent::prelude::unsafe_container_of;
+///
+/// struct Task {
+///     id: u32,
+///     priority: u8,
+/// }
+///
+/// fn main() {
+///     let task = Task { id: 7, priority: 1 };
+///     let priority: *const u8 = &task.priority;
+///     let _ = unsafe_container_of!(priority, Task, id);
+/// }
+/// ```
+///
+/// # Possible violations
+/// The field pointer expression itself is `unsafe`:
+/// ```compile_fail
+#[doc = include_str!("../../negative_tests/sneaky_unsafe_stops_compilation/src/bin/container_of_sneaky_unsafe_ptr.rs")]
+/// ```
+///
+/// # Implementation notes
+/// The field type check passes the given pointer, and a pointer to the field (of a reference to
+/// `Container`), to [crate::backend::expect_same_pointee]. That's in a closure in an inactive `if
+/// false {...}` branch, so it's never evaluated (and it works in `const` contexts, too).
+#[doc(hidden)]
+#[macro_export]
+macro_rules! unsafe_container_of {
+    (@ptr $ptr:ident, $container:ty, $first:tt $(. $rest:tt)*) => {{
+        if false {
+            let _ = |container: &$container| {
+                $crate::backend::expect_same_pointee(
+                    $ptr,
+                    ::core::ptr::addr_of!(container.$first $(. $rest)*),
+                )
+            };
+        }
+        let offset = ::core::mem::offset_of!($container, $first $(. $rest)*);
+        unsafe { $ptr.cast::<u8>().sub(offset) }.cast::<$container>()
+    }};
+
+    (ref mut $ptr:expr, $container:ty, $first:tt $(. $rest:tt)* $(, $lifetime:lifetime)?) => {{
+        let container = $crate::unsafe_container_of!(mut $ptr, $container, $first $(. $rest)*);
+        $crate::unsafe_mut!(container $(, $lifetime)?)
+    }};
+    (ref $ptr:expr, $container:ty, $first:tt $(. $rest:tt)* $(, $lifetime:lifetime)?) => {{
+        let container = $crate::unsafe_container_of!($ptr, $container, $first $(. $rest)*);
+        $crate::unsafe_ref!(container $(, $lifetime)?)
+    }};
+    (mut $ptr:expr, $container:ty, $first:tt $(. $rest:tt)* $(,)?) => {{
+        let ptr = $crate::backend::RawPointer($ptr).into_mut_ptr();
+        $crate::unsafe_container_of!(@ptr ptr, $container, $first $(. $rest)*)
+    }};
+    ($ptr:expr, $container:ty, $first:tt $(. $rest:tt)* $(,)?) => {{
+        let ptr = $crate::backend::RawPointer($ptr).into_const_ptr();
+        $crate::unsafe_container_of!(@ptr ptr, $container, $first $(. $rest)*)
+    }};
+}
@@ -0,0 +1,156 @@
+//! C strings ([core::ffi::CStr]).
+
+/// Get a [core::ffi::CStr] from a pointer to a nul-terminated C string, or from a byte slice that
+/// ends with nul. Isolate `unsafe {...}` only for [core::ffi::CStr::from_ptr] (or
+/// [core::ffi::CStr::from_bytes_with_nul_unchecked]). Evaluate the given pointer (or slice), and
+/// the owner (if any), **outside** `unsafe {...}`.
+///
+/// Syntax (`ptr` is `*const c_char`, `*mut c_char`, `*const u8`, `*mut u8`, or `NonNull` of
+/// [core::ffi::c_char] or [u8]):
+/// - `unsafe_cstr!(ptr)` yields `&CStr` (with an unbounded lifetime, like
+///   [crate::prelude::unsafe_ref]).
+/// - `unsafe_cstr!(ptr, 'a)` yields `&'a CStr`.
+/// - `unsafe_cstr!(ptr, borrowing = &owner)` yields `&CStr` that can't outlive that borrow of
+///   `owner` (see [crate::prelude::unsafe_ref]).
+/// - `unsafe_cstr!(bytes_with_nul = bytes)` for `bytes` being `&[u8]` (or `&[u8; N]`) yields
+///   `&CStr` with the lifetime of `bytes`. In debug builds, it checks that `bytes` ends with nul,
+///   and that it contains no other nul. If not, it panics.
+///
+/// For a pointer that may be null, use [crate::prelude::unsafe_cstr_opt].
+///
+/// Usable in `const` contexts (with Rust 1.81+ for pointers).
+///
+/// ```
+/// use core::ffi::{c_char, CStr};
+/// use prudent::prelude::unsafe_cstr;
+///
+/// const GREETING: &CStr = unsafe_cstr!(bytes_with_nul = b"Hi\0");
+///
+/// /// Imitates a foreign function that returns a static C string.
+/// extern "C" fn version() -> *const c_char {
+///     c"1.0".as_ptr()
+/// }
+///
+/// struct Name {
+///     bytes: Vec<u8>,
+/// }
+/// impl Name {
+///     fn as_cstr(&self) -> &CStr {
+///         unsafe_cstr!(self.bytes.as_ptr(), borrowing = &self.bytes)
+///     }
+/// }
+///
+/// fn main() {
+///     let version: &'static CStr = unsafe_cstr!(version(), 'static);
+///     assert_eq!(version.to_bytes(), b"1.0");
+///     assert_eq!(GREETING.to_bytes(), b"Hi");
+///
+///     let name = Name { bytes: b"prudent\0".to_vec() };
+///     assert_eq!(name.as_cstr().to_str(), Ok("prudent"));
+/// }
+/// ```
+///
+/// Only pointers to [core::ffi::c_char] or [u8] are accepted:
+/// ```compile_fail,E0599
+/// use prudent::prelude::unsafe_cstr;
+///
+/// fn main() {
+///     let wide = [0u16];
+///     let _ = unsafe_cstr!(wide.as_ptr());
+/// }
+/// ```
+///
+/// # Possible violations
+/// The byte slice expression itself is `unsafe`:
+/// ```compile_fail
+#[doc = include_str!("../../negative_tests/sneaky_unsafe_stops_compilation/src/bin/cstr_sneaky_unsafe_bytes.rs")]
+/// ```
+///
+/// # Implementation notes
+/// The pointer is converted with [crate::backend::RawPointer] to `*const u8` or `*const i8`, and
+/// then with [crate::backend::CCharPointer] to `*const c_char`. There is no conversion from other
+/// pointee types, so they don't compile.
+#[doc(hidden)]
+#[macro_export]
+macro_rules! unsafe_cstr {
+    (@ptr $ptr:expr) => {
+        $crate::backend::CCharPointer($crate::backend::RawPointer($ptr).into_const_ptr())
+            .into_c_char_ptr()
+    };
+    (bytes_with_nul = $bytes:expr $(,)?) => {{
+        let bytes: &[u8] = $bytes;
+        ::core::debug_assert!(
+            $crate::backend::is_bytes_with_nul(bytes),
+            "unsafe_cstr!: the bytes must end with nul, and contain no other nul."
+        );
+        unsafe { ::core::ffi::CStr::from_bytes_with_nul_unchecked(bytes) }
+    }};
+    ($ptr:expr, borrowing = $owner:expr $(,)?) => {{
+        let ptr = $crate::unsafe_cstr!(@ptr $ptr);
+        let owner = $owner;
+        $crate::backend::borrowing_ref(owner, unsafe { ::core::ffi::CStr::from_ptr(ptr) })
+    }};
+    ($ptr:expr $(, $lifetime:lifetime)?) => {{
+        let ptr = $crate::unsafe_cstr!(@ptr $ptr);
+        let cstr: &$($lifetime)? ::core::ffi::CStr = unsafe { ::core::ffi::CStr::from_ptr(ptr) };
+        cstr
+    }};
+}
+
+/// Like [crate::prelude::unsafe_cstr], but for a pointer that may be null. Yield
+/// [Option]`<&CStr>`: [None] if the pointer is null.
+///
+/// Same forms as [crate::prelude::unsafe_cstr] for pointers: `unsafe_cstr_opt!(ptr)`,
+/// `unsafe_cstr_opt!(ptr, 'a)` and `unsafe_cstr_opt!(ptr, borrowing = &owner)`.
+///
+/// Usable in `const` contexts (with Rust 1.84+).
+///
+/// ```
+/// use core::ffi::c_char;
+/// use prudent::prelude::unsafe_cstr_opt;
+///
+/// /// Imitates a foreign function that returns a nullable C string.
+/// extern "C" fn getenv_imitation(set: bool) -> *const c_char {
+///     if set {
+///         c"/home/prudent".as_ptr()
+///     } else {
+///         core::ptr::null()
+///     }
+/// }
+///
+/// fn main() {
+///     let home = unsafe_cstr_opt!(getenv_imitation(true), 'static);
+///     assert_eq!(home.map(|home| home.to_bytes()), Some(&b"/home/prudent"[..]));
+///     assert_eq!(unsafe_cstr_opt!(getenv_imitation(false)), None);
+/// }
+/// ```
+///
+/// # Possible violations
+/// The pointer expression itself is `unsafe`:
+/// ```compile_fail
+#[doc = include_str!("../../negative_tests/sneaky_unsafe_stops_compilation/src/bin/cstr_opt_sneaky_unsafe_ptr.rs")]
+/// ```
+#[doc(hidden)]
+#[macro_export]
+macro_rules! unsafe_cstr_opt {
+    ($ptr:expr, borrowing = $owner:expr $(,)?) => {{
+        let ptr = $crate::unsafe_cstr!(@ptr $ptr);
+        let owner = $owner;
+        if ptr.is_null() {
+            ::core::option::Option::None
+        } else {
+            ::core::option::Option::Some($crate::backend::borrowing_ref(owner, unsafe {
+                ::core::ffi::CStr::from_ptr(ptr)
+            }))
+        }
+    }};
+    ($ptr:expr $(, $lifetime:lifetime)?) => {{
+        let ptr = $crate::unsafe_cstr!(@ptr $ptr);
+        let cstr: ::core::option::Option<&$($lifetime)? ::core::ffi::CStr> = if ptr.is_null() {
+            ::core::option::Option::None
+        } else {
+            ::core::option::Option::Some(unsafe { ::core::ffi::CStr::from_ptr(ptr) })
+        };
+        cstr
+    }};
+}
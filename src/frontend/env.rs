@@ -0,0 +1,117 @@
+//! Setting and removing environment variables. Only with feature `std`.
+
+/// Set an environment variable of the current process. Isolate `unsafe {...}` only for
+/// [std::env::set_var] (which is `unsafe` since edition 2024). Evaluate the key and the value
+/// **outside** `unsafe {...}`. Only with feature `std`.
+///
+/// Syntax:
+/// - `unsafe_set_var!(key, value)`, where both `key` and `value` are `AsRef<OsStr>` (like `&str`
+///   or `String`).
+/// - `unsafe_set_var!(key, value, single_threaded)` does the same, but in debug builds it first
+///   asserts that the process has no thread other than the current one. Otherwise it panics
+///   (rather than racing with other threads that could read the environment). The main thread is
+///   not counted if it's not the current one, so that this works in tests (where `libtest` runs
+///   each test in a spawned thread, and its main thread waits for it). Tests that run at the same
+///   time are counted, so use this in a test binary with only one `#[test]` (or run it with
+///   `--test-threads=1`). The number of threads is checked on Linux (and Android) only, through
+///   `/proc`, and not under Miri.
+///
+/// ```
+/// use prudent::prelude::{unsafe_remove_var, unsafe_set_var};
+///
+/// fn main() {
+///     // A doctest runs as a separate process, in its main thread, so `single_threaded` holds.
+///     let suffix = "SET_VAR";
+///     unsafe_set_var!(format!("PRUDENT_DOCTEST_{suffix}"), "on", single_threaded);
+///     assert_eq!(std::env::var("PRUDENT_DOCTEST_SET_VAR").as_deref(), Ok("on"));
+///
+///     unsafe_remove_var!("PRUDENT_DOCTEST_SET_VAR", single_threaded);
+///     assert!(std::env::var_os("PRUDENT_DOCTEST_SET_VAR").is_none());
+/// }
+/// ```
+///
+/// # Possible violations
+/// The value expression itself is `unsafe`:
+/// ```compile_fail
+#[doc = include_str!("../../negative_tests/sneaky_unsafe_stops_compilation/src/bin/set_var_sneaky_unsafe_value.rs")]
+/// ```
+///
+/// # Implementation notes
+/// `std` is referred to through [crate::backend], so that the caller doesn't need `extern crate
+/// std` (in a `no_std` crate).
+///
+/// Before edition 2024, [std::env::set_var] is safe, and so is its call from this macro (whose
+/// code is in edition 2021). Hence `#[allow(unused_unsafe)]`.
+///
+/// The check is done by [crate::backend::assert_single_threaded]. It's called under `if
+/// cfg!(debug_assertions)`, like [core::debug_assert].
+#[doc(hidden)]
+#[macro_export]
+macro_rules! unsafe_set_var {
+    (@set $key:ident, $value:ident) => {
+        #[allow(unused_unsafe)]
+        unsafe {
+            $crate::backend::std::env::set_var($key, $value)
+        }
+    };
+    ($key:expr, $value:expr, single_threaded $(,)?) => {{
+        let (key, value) = ($key, $value);
+        if ::core::cfg!(debug_assertions) {
+            $crate::backend::assert_single_threaded("unsafe_set_var!");
+        }
+        $crate::unsafe_set_var!(@set key, value)
+    }};
+    ($key:expr, $value:expr $(,)?) => {{
+        let (key, value) = ($key, $value);
+        $crate::unsafe_set_var!(@set key, value)
+    }};
+}
+
+/// Remove an environment variable of the current process. Isolate `unsafe {...}` only for
+/// [std::env::remove_var] (which is `unsafe` since edition 2024). Evaluate the key **outside**
+/// `unsafe {...}`. Only with feature `std`.
+///
+/// Syntax:
+/// - `unsafe_remove_var!(key)`, where `key` is `AsRef<OsStr>` (like `&str` or `String`).
+/// - `unsafe_remove_var!(key, single_threaded)` does the same, but in debug builds it first
+///   asserts that the process has only one thread, like [crate::prelude::unsafe_set_var].
+///
+/// ```
+/// use prudent::prelude::{unsafe_remove_var, unsafe_set_var};
+///
+/// fn main() {
+///     unsafe_set_var!("PRUDENT_DOCTEST_REMOVE_VAR", "1");
+///     unsafe_remove_var!("PRUDENT_DOCTEST_REMOVE_VAR");
+///     assert!(std::env::var_os("PRUDENT_DOCTEST_REMOVE_VAR").is_none());
+/// }
+/// ```
+///
+/// # Possible violations
+/// The key expression itself is `unsafe`:
+/// ```compile_fail
+#[doc = include_str!("../../negative_tests/sneaky_unsafe_stops_compilation/src/bin/remove_var_sneaky_unsafe_key.rs")]
+/// ```
+///
+/// # Implementation notes
+/// See [crate::prelude::unsafe_set_var].
+#[doc(hidden)]
+#[macro_export]
+macro_rules! unsafe_remove_var {
+    (@remove $key:ident) => {
+        #[allow(unused_unsafe)]
+        unsafe {
+            $crate::backend::std::env::remove_var($key)
+        }
+    };
+    ($key:expr, single_threaded $(,)?) => {{
+        let key = $key;
+        if ::core::cfg!(debug_assertions) {
+            $crate::backend::assert_single_threaded("unsafe_remove_var!");
+        }
+        $crate::unsafe_remove_var!(@remove key)
+    }};
+    ($key:expr $(,)?) => {{
+        let key = $key;
+        $crate::unsafe_remove_var!(@remove key)
+    }};
+}
@@ -0,0 +1,152 @@
+//! Statics declared in `extern` blocks.
+
+/// Read, reference, or assign to, a `static` declared in an `extern` block (or its
+/// (sub...-)field or array item). Isolate `unsafe {...}` only for the access itself. Evaluate any
+/// index expressions, and the assigned value, **outside** `unsafe {...}`.
+///
+/// Syntax (`STATIC` is a path to a `static` or `static mut` declared in an `extern` block):
+/// - `unsafe_extern_static!(STATIC)` reads the (whole) value. For [Copy] types only.
+/// - `unsafe_extern_static!(STATIC => .field[index]...)` reads a field or an item. The path after
+///   `=>` has the same syntax as in [crate::prelude::unsafe_field].
+/// - `unsafe_extern_static!(ref STATIC)` and `unsafe_extern_static!(ref STATIC => .field...)`
+///   yield a `&'static` reference.
+/// - `unsafe_extern_static!(STATIC = value)` and `unsafe_extern_static!(STATIC => .field... =
+///   value)` assign (dropping the old value). Only for a `static mut`.
+///
+/// Accessing an extern static is `unsafe`, because the compiler can't verify its type, nor that
+/// foreign code doesn't modify it at the same time. A `&'static` reference to a `static mut` is
+/// sound only if nothing modifies it while the reference is in use.
+///
+/// For `static mut` variables defined in Rust, see [crate::prelude::unsafe_static_set].
+///
+/// ```
+/// # #[cfg(target_os = "linux")]
+/// # mod linux {
+/// use core::ffi::c_char;
+/// use prudent::prelude::{unsafe_cstr_opt, unsafe_extern_static, unsafe_val};
+///
+/// extern "C" {
+///     /// The environment, provided by the C library (glibc or musl).
+///     static environ: *const *const c_char;
+/// }
+///
+/// pub fn main() {
+///     let variables: *const *const c_char = unsafe_extern_static!(environ);
+///     if !variables.is_null() {
+///         if let Some(first) = unsafe_cstr_opt!(unsafe_val!(variables)) {
+///             assert!(first.to_bytes().contains(&b'='));
+///         }
+///     }
+/// }
+/// # }
+/// # fn main() {
+/// #     #[cfg(target_os = "linux")]
+/// #     linux::main();
+/// # }
+/// ```
+///
+/// ```
+/// # // Miri doesn't support `extern` statics (other than a few from the C library).
+/// # #[cfg(not(miri))]
+/// # mod not_miri {
+/// use prudent::prelude::unsafe_extern_static;
+///
+/// #[repr(C)]
+/// pub struct Registers {
+///     pub status: u32,
+///     pub data: [u8; 4],
+/// }
+///
+/// /// Imitates a static defined in foreign code.
+/// mod foreign {
+///     #[no_mangle]
+///     pub static mut PRUDENT_DOC_REGISTERS: super::Registers =
+///         super::Registers { status: 1, data: [0; 4] };
+/// }
+///
+/// extern "C" {
+///     static mut PRUDENT_DOC_REGISTERS: Registers;
+/// }
+///
+/// pub fn main() {
+///     let last = 3;
+///     unsafe_extern_static!(PRUDENT_DOC_REGISTERS => .data[last] = 9);
+///     assert_eq!(unsafe_extern_static!(PRUDENT_DOC_REGISTERS => .status), 1);
+///     let data: &'static [u8; 4] = unsafe_extern_static!(ref PRUDENT_DOC_REGISTERS => .data);
+///     assert_eq!(data, &[0, 0, 0, 9]);
+/// }
+/// # }
+/// # fn main() {
+/// #     #[cfg(not(miri))]
+/// #     not_miri::main();
+/// # }
+/// ```
+///
+/// # Possible violations
+/// An index expression itself is `unsafe`:
+/// ```compile_fail
+#[doc = include_str!("../../negative_tests/sneaky_unsafe_stops_compilation/src/bin/extern_static_sneaky_unsafe_index.rs")]
+/// ```
+///
+/// # Implementation notes
+/// This takes the address of the static with [core::ptr::addr_of] (or [core::ptr::addr_of_mut])
+/// in `unsafe {...}`. (That is safe since Rust 1.82, hence `#[allow(unused_unsafe)]`.) Then it
+/// forwards the pointer to [crate::prelude::unsafe_field] (for the path, if any), and to
+/// [crate::prelude::unsafe_val], [crate::prelude::unsafe_ref] or [crate::prelude::unsafe_set].
+///
+/// To tell a read from an assignment, this is a "TT muncher": it collects the path up to a lone
+/// `=` (if any).
+#[doc(hidden)]
+#[macro_export]
+macro_rules! unsafe_extern_static {
+    (@ptr mut $static:path) => {{
+        #[allow(unused_unsafe)]
+        let ptr = unsafe { ::core::ptr::addr_of_mut!($static) };
+        ptr
+    }};
+    (@ptr $static:path) => {{
+        #[allow(unused_unsafe)]
+        let ptr = unsafe { ::core::ptr::addr_of!($static) };
+        ptr
+    }};
+
+    (@path val $static:path [$($path:tt)+] = $value:expr) => {{
+        let ptr = $crate::unsafe_extern_static!(@ptr mut $static);
+        $crate::unsafe_set!(ptr => $($path)+, $value)
+    }};
+    (@path ref $static:path [$($path:tt)+] = $($value:tt)*) => {
+        ::core::compile_error!("unsafe_extern_static! can't assign with `ref`.")
+    };
+    (@path $kind:tt $static:path [$($path:tt)*] $token:tt $($rest:tt)*) => {
+        $crate::unsafe_extern_static!(@path $kind $static [$($path)* $token] $($rest)*)
+    };
+    (@path val $static:path [$($path:tt)+]) => {{
+        let ptr = $crate::unsafe_extern_static!(@ptr $static);
+        let ptr = $crate::unsafe_field!(ptr => $($path)+);
+        $crate::unsafe_val!(ptr)
+    }};
+    (@path ref $static:path [$($path:tt)+]) => {{
+        let ptr = $crate::unsafe_extern_static!(@ptr $static);
+        let ptr = $crate::unsafe_field!(ptr => $($path)+);
+        $crate::unsafe_ref!(ptr, 'static)
+    }};
+
+    (ref $static:path => $($path:tt)+) => {
+        $crate::unsafe_extern_static!(@path ref $static [] $($path)+)
+    };
+    (ref $static:path) => {{
+        let ptr = $crate::unsafe_extern_static!(@ptr $static);
+        $crate::unsafe_ref!(ptr, 'static)
+    }};
+    ($static:path => $($path:tt)+) => {
+        $crate::unsafe_extern_static!(@path val $static [] $($path)+)
+    };
+    ($static:path = $value:expr) => {{
+        let ptr = $crate::unsafe_extern_static!(@ptr mut $static);
+        $crate::unsafe_set!(ptr, $value)
+    }};
+    ($static:path) => {{
+        let ptr = $crate::unsafe_extern_static!(@ptr $static);
+        $crate::unsafe_val!(ptr)
+    }};
+}
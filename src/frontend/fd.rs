@@ -0,0 +1,141 @@
+//! Taking ownership of, or borrowing, raw file descriptors. Only with feature `std`, and only on
+//! Unix.
+
+/// Take ownership of a raw file descriptor, as a given type: [OwnedFd](std::os::fd::OwnedFd),
+/// [File](std::fs::File), [TcpStream](std::net::TcpStream),
+/// [UnixStream](std::os::unix::net::UnixStream) or any other [FromRawFd](std::os::fd::FromRawFd).
+/// Isolate `unsafe {...}` only for `from_raw_fd`. Evaluate the file descriptor **outside** `unsafe
+/// {...}`. Only with feature `std`, and only on Unix.
+///
+/// Syntax: `unsafe_from_raw_fd!(Type, fd)` for `fd` being [RawFd](std::os::fd::RawFd). Yield
+/// `Type`.
+///
+/// In debug builds, this first checks (with `fcntl(fd, F_GETFD)`) that `fd` is open. Otherwise it
+/// panics. (It can't check that nothing else owns `fd`.)
+///
+/// ```
+/// # // Miri (with isolation) can't open files.
+/// # #[cfg(all(unix, not(miri)))]
+/// # mod unix {
+/// use prudent::prelude::unsafe_from_raw_fd;
+/// use std::fs::File;
+/// use std::io::{Read, Write};
+/// use std::os::fd::{IntoRawFd, OwnedFd};
+///
+/// pub fn main() {
+///     let path = std::env::temp_dir().join("prudent_doctest_unsafe_from_raw_fd");
+///     let fd = File::create(&path).unwrap().into_raw_fd();
+///     // Imitate a file descriptor that came from `libc`.
+///     let owned = unsafe_from_raw_fd!(OwnedFd, fd);
+///
+///     let mut file = File::from(owned);
+///     file.write_all(b"prudent").unwrap();
+///     drop(file);
+///
+///     let fd = File::open(&path).unwrap().into_raw_fd();
+///     let mut file = unsafe_from_raw_fd!(File, fd);
+///     let mut content = String::new();
+///     file.read_to_string(&mut content).unwrap();
+///     assert_eq!(content, "prudent");
+///     std::fs::remove_file(path).unwrap();
+/// }
+/// # }
+/// # fn main() {
+/// #     #[cfg(all(unix, not(miri)))]
+/// #     unix::main();
+/// # }
+/// ```
+///
+/// # Possible violations
+/// The file descriptor expression itself is `unsafe`:
+/// ```compile_fail
+#[doc = include_str!("../../negative_tests/sneaky_unsafe_stops_compilation/src/bin/from_raw_fd_sneaky_unsafe_fd.rs")]
+/// ```
+///
+/// # Implementation notes
+/// `Type` is resolved **outside** `unsafe {...}`, too: `<Type as FromRawFd>::from_raw_fd` is
+/// stored in a local `unsafe fn` pointer first. (Otherwise a type could carry `unsafe` code in a
+/// `const` generic argument.)
+///
+/// The check is done by [crate::backend::expect_open_fd]. It's called under `if
+/// cfg!(debug_assertions)`, like [core::debug_assert].
+#[doc(hidden)]
+#[macro_export]
+macro_rules! unsafe_from_raw_fd {
+    ($type:ty, $fd:expr $(,)?) => {{
+        let fd: $crate::backend::std::os::fd::RawFd = $fd;
+        let from_raw_fd: unsafe fn($crate::backend::std::os::fd::RawFd) -> $type =
+            <$type as $crate::backend::std::os::fd::FromRawFd>::from_raw_fd;
+        if ::core::cfg!(debug_assertions) {
+            $crate::backend::expect_open_fd(fd, "unsafe_from_raw_fd!");
+        }
+        unsafe { from_raw_fd(fd) }
+    }};
+}
+
+/// Borrow a raw file descriptor as [BorrowedFd](std::os::fd::BorrowedFd), without taking
+/// ownership. Isolate `unsafe {...}` only for
+/// [BorrowedFd::borrow_raw](std::os::fd::BorrowedFd::borrow_raw). Evaluate the file descriptor
+/// **outside** `unsafe {...}`. Only with feature `std`, and only on Unix.
+///
+/// Syntax:
+/// - `unsafe_borrow_raw_fd!(fd)` for `fd` being [RawFd](std::os::fd::RawFd). Yield `BorrowedFd`
+///   with an inferred lifetime.
+/// - `unsafe_borrow_raw_fd!(fd, 'a)`. Yield `BorrowedFd<'a>`.
+///
+/// In debug builds, this first checks (with `fcntl(fd, F_GETFD)`) that `fd` is open, like
+/// [crate::prelude::unsafe_from_raw_fd].
+///
+/// ```
+/// # #[cfg(unix)]
+/// # mod unix {
+/// use prudent::prelude::unsafe_borrow_raw_fd;
+/// use std::os::fd::{AsRawFd, BorrowedFd, RawFd};
+///
+/// /// Imitates a callback that receives a file descriptor that it doesn't own.
+/// fn is_terminal(fd: RawFd) -> bool {
+///     use std::io::IsTerminal;
+///     let borrowed = unsafe_borrow_raw_fd!(fd);
+///     borrowed.is_terminal()
+/// }
+///
+/// fn borrow_stdout<'a>() -> BorrowedFd<'a> {
+///     unsafe_borrow_raw_fd!(std::io::stdout().as_raw_fd(), 'a)
+/// }
+///
+/// pub fn main() {
+///     let _ = is_terminal(std::io::stderr().as_raw_fd());
+///     let owned = borrow_stdout().try_clone_to_owned().unwrap();
+///     drop(owned);
+/// }
+/// # }
+/// # fn main() {
+/// #     #[cfg(unix)]
+/// #     unix::main();
+/// # }
+/// ```
+///
+/// # Possible violations
+/// The file descriptor expression itself is `unsafe`:
+/// ```compile_fail
+#[doc = include_str!("../../negative_tests/sneaky_unsafe_stops_compilation/src/bin/borrow_raw_fd_sneaky_unsafe_fd.rs")]
+/// ```
+///
+/// # Implementation notes
+/// The check is done by [crate::backend::expect_open_fd], under `if cfg!(debug_assertions)`.
+#[doc(hidden)]
+#[macro_export]
+macro_rules! unsafe_borrow_raw_fd {
+    ($fd:expr, $lifetime:lifetime $(,)?) => {{
+        let borrowed: $crate::backend::std::os::fd::BorrowedFd<$lifetime> =
+            $crate::unsafe_borrow_raw_fd!($fd);
+        borrowed
+    }};
+    ($fd:expr $(,)?) => {{
+        let fd: $crate::backend::std::os::fd::RawFd = $fd;
+        if ::core::cfg!(debug_assertions) {
+            $crate::backend::expect_open_fd(fd, "unsafe_borrow_raw_fd!");
+        }
+        unsafe { $crate::backend::std::os::fd::BorrowedFd::borrow_raw(fd) }
+    }};
+}
@@ -0,0 +1,98 @@
+//! Raw pointer projections to (sub...-)fields and array/slice items.
+
+/// Project a raw pointer to a (nested) field, and/or an array/slice item, of its pointee. Yield a
+/// raw pointer to that place, **without** creating any intermediate reference. Isolate `unsafe
+/// {...}` only for the projection itself. Evaluate the given pointer, and any index expressions,
+/// **outside** `unsafe {...}`.
+///
+/// Syntax:
+/// - `unsafe_field!(ptr => .field.sub_field[index]...)` for `ptr` being `*const T`, `*mut T` or
+///   `NonNull<T>`. Yield `*const F`. Same as `unsafe { &raw const (*ptr).field.sub_field[index] }`.
+/// - `unsafe_field!(mut ptr => .field.sub_field[index]...)` for `ptr` being `*mut T` or
+///   `NonNull<T>`. Yield `*mut F`. Same as `unsafe { &raw mut (*ptr).field.sub_field[index] }`.
+///
+/// The path after `=>` consists of any number (at least one) of:
+/// - `.field` - a named field, or `.0`, `.1`... - a tuple (struct) field, and
+/// - `[index]` - an item of an array or slice. `index` is evaluated to a local `usize` variable.
+///
+/// Unlike [crate::prelude::unsafe_ref] and [crate::prelude::unsafe_mut], this doesn't create a
+/// reference, not even for a moment. So it's suitable for partially initialized memory, or for
+/// memory that may be aliased.
+///
+/// Indexing an array or slice is built in (it doesn't create a reference), but it's bound-checked
+/// (so it may panic). However, any type that implements [core::ops::Index] (for example, `Vec`),
+/// or [core::ops::Deref] (except for `Box`), creates a reference - so don't project through such
+/// types.
+///
+/// Usable in `const` contexts.
+///
+/// ```
+/// use prudent::prelude::{unsafe_field, unsafe_val};
+///
+/// struct Image {
+///     size: (u16, u16),
+///     pixels: [[u8; 3]; 4],
+/// }
+///
+/// const IMAGE: Image = Image {
+///     size: (2, 2),
+///     pixels: [[0, 0, 0], [255, 0, 0], [0, 255, 0], [0, 0, 255]],
+/// };
+/// const HEIGHT: *const u16 = unsafe_field!(&IMAGE as *const Image => .size.1);
+///
+/// fn main() {
+///     let mut image = IMAGE;
+///     let image: *mut Image = &mut image;
+///
+///     let green: *mut u8 = unsafe_field!(mut image => .pixels[2][1]);
+///     let last = 3;
+///     let blue: *const u8 = unsafe_field!(image => .pixels[last][2]);
+///     assert_eq!((unsafe_val!(green), unsafe_val!(blue)), (255, 255));
+///     # let _ = HEIGHT;
+/// }
+/// ```
+///
+/// # Possible violations
+/// An index expression itself is `unsafe`:
+/// ```compile_fail
+#[doc = include_str!("../../negative_tests/sneaky_unsafe_stops_compilation/src/bin/field_sneaky_unsafe_index.rs")]
+/// ```
+///
+/// # Implementation notes
+/// This is a "TT muncher". It collects the projection (`.field` and `[index]` parts). For each
+/// `[index]` it generates a nested block with a `let` binding. The local variables are hygienic,
+/// so they don't conflict with the user's variables (nor with each other). Finally, inside all
+/// those nested blocks, it generates [core::ptr::addr_of] or [core::ptr::addr_of_mut] in `unsafe
+/// {...}`.
+#[doc(hidden)]
+#[macro_export]
+macro_rules! unsafe_field {
+    (@path $mut:tt $ptr:ident [$($projection:tt)*] . $field:tt $($rest:tt)*) => {
+        $crate::unsafe_field!(@path $mut $ptr [$($projection)* . $field] $($rest)*)
+    };
+    (@path $mut:tt $ptr:ident [$($projection:tt)*] [$index:expr] $($rest:tt)*) => {{
+        let index: usize = $index;
+        $crate::unsafe_field!(@path $mut $ptr [$($projection)* [index]] $($rest)*)
+    }};
+    (@path const $ptr:ident [$($projection:tt)+]) => {
+        unsafe { ::core::ptr::addr_of!((*$ptr) $($projection)+) }
+    };
+    (@path mut $ptr:ident [$($projection:tt)+]) => {
+        unsafe { ::core::ptr::addr_of_mut!((*$ptr) $($projection)+) }
+    };
+    (@path $mut:tt $ptr:ident [$($projection:tt)*] $($rest:tt)*) => {
+        ::core::compile_error!(::core::concat!(
+            "unsafe_field! expects a path of `.field` and `[index]` parts, but it got: ",
+            ::core::stringify!($($rest)*)
+        ))
+    };
+
+    (mut $ptr:expr => $($path:tt)+) => {{
+        let ptr = $crate::backend::RawPointer($ptr).into_mut_ptr();
+        $crate::unsafe_field!(@path mut ptr [] $($path)+)
+    }};
+    ($ptr:expr => $($path:tt)+) => {{
+        let ptr = $crate::backend::RawPointer($ptr).into_const_ptr();
+        $crate::unsafe_field!(@path const ptr [] $($path)+)
+    }};
+}
@@ -0,0 +1,41 @@
+//! Invoking `unsafe` functions, including function pointers stored in fields, arrays/slices and
+//! [Option]s.
+
+/// See [crate::prelude::unsafe_fn].
+///
+/// # Implementation notes
+/// If `$fn` is a path, this forwards all input to `unsafe_fn` from `prudent-macros-enforce` (or
+/// `prudent-macros-lint`), as is.
+///
+/// Otherwise `$fn` is an expression (for example, `table.init`, `handlers[i]` or
+/// `maybe_handler.unwrap()`). Then, before forwarding, this injects `$fn` in a closure in an
+/// inactive `if false {...}` branch, **outside** `unsafe {...}`, and it invokes
+/// [crate::backend::ExpectedUnsafeFnPointerButReceivedSafe::prudent_conflict_for_safe_fn_pointer]
+/// on it. That fails to compile if the function (pointer) is safe, regardless of its number of
+/// arguments (up to 12).
+#[doc(hidden)]
+#[macro_export]
+macro_rules! unsafe_fn {
+    (@callee [$($tokens:tt)+] $fn:path $(; $($args:tt)*)?) => {
+        $crate::backend::prudent_macros::unsafe_fn!($($tokens)+)
+    };
+    (@callee [$($tokens:tt)+] $fn:expr $(; $($args:tt)*)?) => {
+        if false {
+            // A closure, so that this works in `const` contexts, too.
+            let _ = || {
+                #[allow(unused_imports, clippy::unsafe_removed_from_name)]
+                use $crate::backend::{
+                    ExpectedUnsafeFnPointerButReceivedSafe as _,
+                    FailsWithConflictForSafeFnPointer as _,
+                };
+                (&$fn).prudent_conflict_for_safe_fn_pointer();
+            };
+            ::core::unreachable!()
+        } else {
+            $crate::backend::prudent_macros::unsafe_fn!($($tokens)+)
+        }
+    };
+    ($($tokens:tt)+) => {
+        $crate::unsafe_fn!(@callee [$($tokens)+] $($tokens)+)
+    };
+}
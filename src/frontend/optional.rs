@@ -0,0 +1,126 @@
+//! Dereferencing nullable raw pointers.
+
+/// Like [crate::prelude::unsafe_ref], but for a pointer that may be null. Yield
+/// [Option]`<&T>`: [None] if the pointer is null. Isolate `unsafe {...}` only for
+/// `<*const T>::as_ref`. Evaluate the given pointer **outside** `unsafe {...}`.
+///
+/// Same forms as [crate::prelude::unsafe_ref]:
+/// - `unsafe_ref_opt!(pt)` for `pt` being `*const T` or `*mut T`, including slices `[T]` and `dyn
+///   Trait`.
+/// - `unsafe_ref_opt!(pt, 'a)` yields `Option<&'a T>`.
+/// - `unsafe_ref_opt!(pt, Type)` coerces `pt` to `*const Type` first. `Type` may be a slice
+///   `[...]`, or it may start with `dyn`. Like with [crate::prelude::unsafe_ref], only the identity
+///   or an unsizing coercion is allowed.
+///
+/// Unlike [crate::prelude::unsafe_ref], the result doesn't coerce from `Option<&T>` to (for
+/// example) `Option<&dyn Trait>`. Use the typed form for that.
+///
+/// Usable in `const` contexts (with Rust 1.84+).
+///
+/// ```
+/// use core::ffi::c_char;
+/// use prudent::prelude::unsafe_ref_opt;
+///
+/// #[repr(C)]
+/// struct Config {
+///     verbose: bool,
+/// }
+///
+/// /// Imitates a callback from foreign code, which may pass a null pointer.
+/// extern "C" fn on_start(config: *const Config) -> c_char {
+///     match unsafe_ref_opt!(config) {
+///         Some(config) if config.verbose => 2,
+///         Some(_) => 1,
+///         None => 0,
+///     }
+/// }
+///
+/// const NULL: Option<&u8> = unsafe_ref_opt!(core::ptr::null::<u8>());
+///
+/// fn main() {
+///     assert_eq!(on_start(&Config { verbose: true }), 2);
+///     assert_eq!(on_start(core::ptr::null()), 0);
+///     assert!(NULL.is_none());
+/// }
+/// ```
+///
+/// # Possible violations
+/// The pointer expression itself is `unsafe`:
+/// ```compile_fail
+#[doc = include_str!("../../negative_tests/sneaky_unsafe_stops_compilation/src/bin/ref_opt_sneaky_unsafe_ptr.rs")]
+/// ```
+///
+/// A non-pointer (for example, an [Option], which has a safe `as_ref` method):
+/// ```compile_fail,E0308
+/// use prudent::prelude::unsafe_ref_opt;
+///
+/// fn main() {
+///     let _ = unsafe_ref_opt!(Some(1));
+/// }
+/// ```
+#[doc(hidden)]
+#[macro_export]
+macro_rules! unsafe_ref_opt {
+    ($ptr:expr) => {{
+        let ptr: *const _ = $ptr;
+        unsafe { ptr.as_ref() }
+    }};
+    ($ptr:expr, $lifetime:lifetime) => {{
+        let ptr: *const _ = $ptr;
+        let reference: ::core::option::Option<&$lifetime _> = unsafe { ptr.as_ref() };
+        reference
+    }};
+    ($ptr:expr, $type:ty) => {{
+        let ptr: *const $type = $ptr;
+        unsafe { ptr.as_ref() }
+    }};
+}
+
+/// Like [crate::prelude::unsafe_mut], but for a pointer that may be null. Yield
+/// [Option]`<&mut T>`: [None] if the pointer is null. Isolate `unsafe {...}` only for
+/// `<*mut T>::as_mut`. Evaluate the given pointer **outside** `unsafe {...}`.
+///
+/// Same forms as [crate::prelude::unsafe_ref_opt], but for `*mut T` only.
+///
+/// ```
+/// use prudent::prelude::unsafe_mut_opt;
+///
+/// fn increment(counter: *mut u32) -> bool {
+///     if let Some(counter) = unsafe_mut_opt!(counter) {
+///         *counter += 1;
+///         true
+///     } else {
+///         false
+///     }
+/// }
+///
+/// fn main() {
+///     let mut counter = 0;
+///     assert!(increment(&mut counter));
+///     assert!(!increment(core::ptr::null_mut()));
+///     assert_eq!(counter, 1);
+/// }
+/// ```
+///
+/// # Possible violations
+/// The pointer expression itself is `unsafe`:
+/// ```compile_fail
+#[doc = include_str!("../../negative_tests/sneaky_unsafe_stops_compilation/src/bin/mut_opt_sneaky_unsafe_ptr.rs")]
+/// ```
+#[doc(hidden)]
+#[macro_export]
+macro_rules! unsafe_mut_opt {
+    ($ptr:expr) => {{
+        let ptr: *mut _ = $ptr;
+        unsafe { ptr.as_mut() }
+    }};
+    ($ptr:expr, $lifetime:lifetime) => {{
+        let ptr: *mut _ = $ptr;
+        let reference: ::core::option::Option<&$lifetime mut _> = unsafe { ptr.as_mut() };
+        reference
+    }};
+    ($ptr:expr, $type:ty) => {{
+        let ptr: *mut $type = $ptr;
+        unsafe { ptr.as_mut() }
+    }};
+}
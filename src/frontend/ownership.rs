@@ -0,0 +1,197 @@
+//! Transferring ownership of `alloc` types (`Box`, `Rc`, `Arc`, `Vec`, `String`) from raw
+//! pointers. Only with feature `alloc`.
+
+/// Take ownership of a [Box], [Rc](alloc::rc::Rc) or [Arc](alloc::sync::Arc) from a raw pointer
+/// (usually one that came from `into_raw`, possibly across FFI). Isolate `unsafe {...}` only for
+/// `from_raw`. Evaluate the given pointer **outside** `unsafe {...}`.
+///
+/// Syntax:
+/// - `unsafe_from_raw!(Box, ptr)` for `ptr` being `*mut T` or `NonNull<T>`. Yield `Box<T>`.
+/// - `unsafe_from_raw!(Rc, ptr)` for `ptr` being `*const T`, `*mut T` or `NonNull<T>`. Yield
+///   `Rc<T>`.
+/// - `unsafe_from_raw!(Arc, ptr)`, similarly. Yield `Arc<T>`.
+///
+/// `T` may be unsized (for example, a slice or `dyn Trait`).
+///
+/// ```
+/// use prudent::prelude::unsafe_from_raw;
+/// use std::sync::Arc;
+///
+/// /// Imitates a handle passed to, and back from, foreign code.
+/// #[repr(C)]
+/// struct Handle {
+///     id: u32,
+/// }
+///
+/// extern "C" fn create() -> *mut Handle {
+///     Box::into_raw(Box::new(Handle { id: 7 }))
+/// }
+/// extern "C" fn destroy(handle: *mut Handle) -> u32 {
+///     let handle = unsafe_from_raw!(Box, handle);
+///     handle.id
+/// }
+///
+/// fn main() {
+///     let handle = create();
+///     assert_eq!(destroy(handle), 7);
+///
+///     let shared: *const [u8] = Arc::into_raw(Arc::<[u8]>::from(&b"shared"[..]));
+///     let shared = unsafe_from_raw!(Arc, shared);
+///     assert_eq!(&*shared, b"shared");
+/// }
+/// ```
+///
+/// # Possible violations
+/// The pointer expression itself is `unsafe`:
+/// ```compile_fail
+#[doc = include_str!("../../negative_tests/sneaky_unsafe_stops_compilation/src/bin/from_raw_sneaky_unsafe_ptr.rs")]
+/// ```
+///
+/// # Implementation notes
+/// The first token selects the type. (It is matched literally, so it must be `Box`, `Rc` or `Arc`,
+/// not a path or an alias.) The actual types are referred to through `alloc`, re-exported from
+/// [crate::backend], so that the caller doesn't need `extern crate alloc`.
+#[doc(hidden)]
+#[macro_export]
+macro_rules! unsafe_from_raw {
+    (Box, $ptr:expr $(,)?) => {{
+        let ptr = $crate::backend::RawPointer($ptr).into_mut_ptr();
+        unsafe { $crate::backend::alloc::boxed::Box::from_raw(ptr) }
+    }};
+    (Rc, $ptr:expr $(,)?) => {{
+        let ptr = $crate::backend::RawPointer($ptr).into_const_ptr();
+        unsafe { $crate::backend::alloc::rc::Rc::from_raw(ptr) }
+    }};
+    (Arc, $ptr:expr $(,)?) => {{
+        let ptr = $crate::backend::RawPointer($ptr).into_const_ptr();
+        unsafe { $crate::backend::alloc::sync::Arc::from_raw(ptr) }
+    }};
+    ($type:tt, $($rest:tt)*) => {
+        ::core::compile_error!(::core::concat!(
+            "unsafe_from_raw! supports Box, Rc and Arc, but it got: ",
+            ::core::stringify!($type)
+        ))
+    };
+}
+
+/// Take ownership of a [Vec](alloc::vec::Vec) or a [String](alloc::string::String) from its raw
+/// parts: a pointer, a length and a capacity (usually ones that came from `into_raw_parts`, or
+/// from `as_mut_ptr`, `len` and `capacity` of a [core::mem::ManuallyDrop]). Isolate `unsafe {...}`
+/// only for `from_raw_parts`. Evaluate the pointer, the length and the capacity **outside** `unsafe
+/// {...}`.
+///
+/// Syntax:
+/// - `unsafe_from_raw_parts!(Vec, ptr, length, capacity)` for `ptr` being `*mut T` or
+///   `NonNull<T>`. Yield `Vec<T>`.
+/// - `unsafe_from_raw_parts!(String, ptr, length, capacity)` for `ptr` being `*mut u8` or
+///   `NonNull<u8>`. Yield `String`.
+///
+/// In debug builds, this checks that `length` is not larger than `capacity`, and (for `String`)
+/// that the bytes are UTF-8 (like [crate::prelude::unsafe_str]). If not, it panics.
+///
+/// ```
+/// use core::mem::ManuallyDrop;
+/// use prudent::prelude::unsafe_from_raw_parts;
+///
+/// fn main() {
+///     let mut numbers = ManuallyDrop::new(vec![1u16, 2, 3]);
+///     let (ptr, length, capacity) = (numbers.as_mut_ptr(), numbers.len(), numbers.capacity());
+///     let numbers: Vec<u16> = unsafe_from_raw_parts!(Vec, ptr, length - 1, capacity);
+///     assert_eq!(numbers, [1, 2]);
+///
+///     let mut name = ManuallyDrop::new(String::from("prudent"));
+///     let name = unsafe_from_raw_parts!(String, name.as_mut_ptr(), name.len(), name.capacity());
+///     assert_eq!(name, "prudent");
+/// }
+/// ```
+///
+/// # Possible violations
+/// The length expression itself is `unsafe`:
+/// ```compile_fail
+#[doc = include_str!("../../negative_tests/sneaky_unsafe_stops_compilation/src/bin/from_raw_parts_sneaky_unsafe_length.rs")]
+/// ```
+#[doc(hidden)]
+#[macro_export]
+macro_rules! unsafe_from_raw_parts {
+    (@length $length:ident, $capacity:ident) => {
+        ::core::debug_assert!(
+            $length <= $capacity,
+            "unsafe_from_raw_parts!: the length is larger than the capacity."
+        );
+    };
+    (Vec, $ptr:expr, $length:expr, $capacity:expr $(,)?) => {{
+        let ptr = $crate::backend::RawPointer($ptr).into_mut_ptr();
+        let length: usize = $length;
+        let capacity: usize = $capacity;
+        $crate::unsafe_from_raw_parts!(@length length, capacity);
+        unsafe { $crate::backend::alloc::vec::Vec::from_raw_parts(ptr, length, capacity) }
+    }};
+    (String, $ptr:expr, $length:expr, $capacity:expr $(,)?) => {{
+        let ptr: *mut u8 = $crate::backend::RawPointer($ptr).into_mut_ptr();
+        let length: usize = $length;
+        let capacity: usize = $capacity;
+        $crate::unsafe_from_raw_parts!(@length length, capacity);
+        let string = unsafe {
+            $crate::backend::alloc::string::String::from_raw_parts(ptr, length, capacity)
+        };
+        if ::core::cfg!(debug_assertions) {
+            $crate::backend::expect_utf8(string.as_bytes(), "unsafe_from_raw_parts!");
+        }
+        string
+    }};
+    ($type:tt, $($rest:tt)*) => {
+        ::core::compile_error!(::core::concat!(
+            "unsafe_from_raw_parts! supports Vec and String, but it got: ",
+            ::core::stringify!($type)
+        ))
+    };
+}
+
+/// Increment the strong reference count of an [Arc](alloc::sync::Arc) (or an
+/// [Rc](alloc::rc::Rc)) given by a raw pointer (one that came from `into_raw`). Isolate `unsafe
+/// {...}` only for `increment_strong_count`. Evaluate the given pointer **outside** `unsafe {...}`.
+///
+/// Syntax: `unsafe_increment_strong_count!(Arc, ptr)` or `unsafe_increment_strong_count!(Rc,
+/// ptr)`, for `ptr` being `*const T`, `*mut T` or `NonNull<T>`.
+///
+/// Use this to hand out one more owning pointer (for example, to foreign code) while keeping
+/// yours. Each increment must be balanced by a [crate::prelude::unsafe_from_raw] (that is later
+/// dropped).
+///
+/// ```
+/// use prudent::prelude::{unsafe_from_raw, unsafe_increment_strong_count};
+/// use std::sync::Arc;
+///
+/// fn main() {
+///     let raw = Arc::into_raw(Arc::new(String::from("config")));
+///     unsafe_increment_strong_count!(Arc, raw);
+///
+///     let config = unsafe_from_raw!(Arc, raw);
+///     assert_eq!(Arc::strong_count(&config), 2);
+///     # let _drop_for_miri = unsafe_from_raw!(Arc, raw);
+/// }
+/// ```
+///
+/// # Possible violations
+/// The pointer expression itself is `unsafe`:
+/// ```compile_fail
+#[doc = include_str!("../../negative_tests/sneaky_unsafe_stops_compilation/src/bin/increment_strong_count_sneaky_unsafe_ptr.rs")]
+/// ```
+#[doc(hidden)]
+#[macro_export]
+macro_rules! unsafe_increment_strong_count {
+    (Arc, $ptr:expr $(,)?) => {{
+        let ptr = $crate::backend::RawPointer($ptr).into_const_ptr();
+        unsafe { $crate::backend::alloc::sync::Arc::increment_strong_count(ptr) }
+    }};
+    (Rc, $ptr:expr $(,)?) => {{
+        let ptr = $crate::backend::RawPointer($ptr).into_const_ptr();
+        unsafe { $crate::backend::alloc::rc::Rc::increment_strong_count(ptr) }
+    }};
+    ($type:tt, $($rest:tt)*) => {
+        ::core::compile_error!(::core::concat!(
+            "unsafe_increment_strong_count! supports Arc and Rc, but it got: ",
+            ::core::stringify!($type)
+        ))
+    };
+}
@@ -0,0 +1,243 @@
+//! Pinning, and projecting pins to fields, for types that are not [Unpin].
+
+/// Pin a pointer (`&mut T`, `Box<T>`... any [core::ops::Deref]) without requiring its target to be
+/// [Unpin]. Isolate `unsafe {...}` only for [Pin::new_unchecked](core::pin::Pin::new_unchecked).
+/// Evaluate the given pointer **outside** `unsafe {...}`.
+///
+/// Syntax: `unsafe_pin_new!(pointer)`. Yield `Pin<P>`, where `P` is the type of `pointer`.
+///
+/// If the target is (known to be) [Unpin], this fails to compile: use the safe
+/// [Pin::new](core::pin::Pin::new) instead.
+///
+/// ```
+/// use core::marker::PhantomPinned;
+/// use core::pin::Pin;
+/// use prudent::prelude::unsafe_pin_new;
+///
+/// struct Registered {
+///     id: u32,
+///     /// Imitates an address that is registered with some foreign code.
+///     _pinned: PhantomPinned,
+/// }
+///
+/// fn main() {
+///     let mut registered = Registered { id: 1, _pinned: PhantomPinned };
+///     // `registered` is shadowed, so it's never moved again.
+///     let registered: Pin<&mut Registered> = unsafe_pin_new!(&mut registered);
+///     assert_eq!(registered.id, 1);
+/// }
+/// ```
+///
+/// # Possible violations
+/// The pointer expression itself is `unsafe`:
+/// ```compile_fail
+#[doc = include_str!("../../negative_tests/sneaky_unsafe_stops_compilation/src/bin/pin_new_sneaky_unsafe_pointer.rs")]
+/// ```
+///
+/// The target is [Unpin]:
+/// ```compile_fail
+#[doc = include_str!("../../negative_tests/unpin_stops_compilation/src/bin/pin_new_unpin.rs")]
+/// ```
+///
+/// # Implementation notes
+/// The check for [Unpin] is done by
+/// [crate::backend::ExpectedNotUnpinButReceivedUnpin::prudent_conflict_for_unpin], in a closure in
+/// an inactive `if false {...}` branch. It's shared with [crate::prelude::unsafe_pin_get_mut] and
+/// [crate::prelude::unsafe_pin_map] (as internal arm `@refuse_unpin`).
+#[doc(hidden)]
+#[macro_export]
+macro_rules! unsafe_pin_new {
+    (@refuse_unpin $pointer:ident) => {
+        if false {
+            let _ = || {
+                #[allow(unused_imports)]
+                use $crate::backend::{
+                    ExpectedNotUnpinButReceivedUnpin as _, FailsWithConflictForUnpin as _,
+                };
+                (&*$pointer).prudent_conflict_for_unpin();
+            };
+        }
+    };
+    ($pointer:expr $(,)?) => {{
+        let pointer = $pointer;
+        $crate::unsafe_pin_new!(@refuse_unpin pointer);
+        unsafe { ::core::pin::Pin::new_unchecked(pointer) }
+    }};
+}
+
+/// Get a mutable reference out of `Pin<&mut T>`, where `T` is not [Unpin]. Isolate `unsafe {...}`
+/// only for [Pin::get_unchecked_mut](core::pin::Pin::get_unchecked_mut). Evaluate the given pin
+/// **outside** `unsafe {...}`.
+///
+/// Syntax: `unsafe_pin_get_mut!(pin)` for `pin` being `Pin<&mut T>`. Yield `&mut T`. To keep
+/// using a `Pin` afterwards (for example, `self` in [core::future::Future::poll]), pass
+/// `pin.as_mut()`.
+///
+/// If `T` is (known to be) [Unpin], this fails to compile: use the safe
+/// [Pin::get_mut](core::pin::Pin::get_mut) instead. (In generic code, where `T` may or may not be
+/// [Unpin], this compiles.)
+///
+/// For a `Future` combinator, see [crate::prelude::unsafe_pin_map].
+///
+/// ```
+/// use core::marker::PhantomPinned;
+/// use core::pin::{pin, Pin};
+/// use prudent::prelude::unsafe_pin_get_mut;
+///
+/// struct Counter {
+///     count: u32,
+///     _pinned: PhantomPinned,
+/// }
+/// impl Counter {
+///     fn increment(self: Pin<&mut Self>) -> u32 {
+///         // The count is not structurally pinned, so it may be modified through `&mut Self`.
+///         let this = unsafe_pin_get_mut!(self);
+///         this.count += 1;
+///         this.count
+///     }
+/// }
+///
+/// fn main() {
+///     let mut counter = pin!(Counter { count: 0, _pinned: PhantomPinned });
+///     counter.as_mut().increment();
+///     assert_eq!(counter.as_mut().increment(), 2);
+/// }
+/// ```
+///
+/// # Possible violations
+/// The pin expression itself is `unsafe`:
+/// ```compile_fail
+#[doc = include_str!("../../negative_tests/sneaky_unsafe_stops_compilation/src/bin/pin_get_mut_sneaky_unsafe_pin.rs")]
+/// ```
+///
+/// `T` is [Unpin]:
+/// ```compile_fail
+#[doc = include_str!("../../negative_tests/unpin_stops_compilation/src/bin/pin_get_mut_unpin.rs")]
+/// ```
+///
+/// # Implementation notes
+/// See [crate::prelude::unsafe_pin_new].
+#[doc(hidden)]
+#[macro_export]
+macro_rules! unsafe_pin_get_mut {
+    ($pin:expr $(,)?) => {{
+        let pin: ::core::pin::Pin<&mut _> = $pin;
+        $crate::unsafe_pin_new!(@refuse_unpin pin);
+        unsafe { ::core::pin::Pin::get_unchecked_mut(pin) }
+    }};
+}
+
+/// Project a pin to a (nested) field of its target, where neither the target nor the field is
+/// [Unpin] ("structural pinning"). Isolate `unsafe {...}` only for
+/// [Pin::map_unchecked_mut](core::pin::Pin::map_unchecked_mut) (or
+/// [Pin::map_unchecked](core::pin::Pin::map_unchecked)). Evaluate the given pin, and the given
+/// projection closure (if any), **outside** `unsafe {...}`.
+///
+/// Syntax:
+/// - `unsafe_pin_map!(mut pin => .field.sub_field...)` for `pin` being `Pin<&mut T>`. Yield
+///   `Pin<&mut F>`.
+/// - `unsafe_pin_map!(pin => .field.sub_field...)` for `pin` being `Pin<&T>`. Yield `Pin<&F>`.
+/// - `unsafe_pin_map!(mut pin, |value| &mut ...)` with a projection closure. Yield `Pin<&mut F>`.
+/// - `unsafe_pin_map!(pin, |value| &...)` with a projection closure. Yield `Pin<&F>`.
+///
+/// The path after `=>` consists of any number (at least one) of `.field` (a named field), or `.0`,
+/// `.1`... (a tuple (struct) field). Don't project through [core::ops::Deref] (for example,
+/// through a `Box` field): its target is not structurally pinned.
+///
+/// If `T`, or the field, is (known to be) [Unpin], this fails to compile. (In generic code, where
+/// they may or may not be [Unpin], this compiles.) For an [Unpin] target, use the safe
+/// [Pin::get_mut](core::pin::Pin::get_mut) and [Pin::new](core::pin::Pin::new) instead. For an
+/// [Unpin] field, get a plain reference with [crate::prelude::unsafe_pin_get_mut] instead.
+///
+/// A `Future` combinator, which applies a function to the output of another `Future`:
+/// ```
+/// use core::future::Future;
+/// use core::pin::Pin;
+/// use core::task::{Context, Poll};
+/// use prudent::prelude::{unsafe_pin_get_mut, unsafe_pin_map};
+///
+/// struct Map<Fut, F> {
+///     /// Structurally pinned.
+///     future: Fut,
+///     /// Not structurally pinned.
+///     f: Option<F>,
+/// }
+///
+/// impl<Fut: Future, F: FnOnce(Fut::Output) -> T, T> Future for Map<Fut, F> {
+///     type Output = T;
+///     fn poll(mut self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<T> {
+///         let future: Pin<&mut Fut> = unsafe_pin_map!(mut self.as_mut() => .future);
+///         match future.poll(cx) {
+///             Poll::Ready(output) => {
+///                 let f = unsafe_pin_get_mut!(self).f.take();
+///                 Poll::Ready(f.expect("polled after completion")(output))
+///             }
+///             Poll::Pending => Poll::Pending,
+///         }
+///     }
+/// }
+/// # fn main() {
+/// #     let _ = Map { future: core::future::ready(1), f: Some(|n: u8| n + 1) };
+/// # }
+/// ```
+///
+/// # Possible violations
+/// The pin expression itself is `unsafe`:
+/// ```compile_fail
+#[doc = include_str!("../../negative_tests/sneaky_unsafe_stops_compilation/src/bin/pin_map_sneaky_unsafe_pin.rs")]
+/// ```
+///
+/// The projection closure contains `unsafe` code (here: an access to a union field):
+/// ```compile_fail
+#[doc = include_str!("../../negative_tests/sneaky_unsafe_stops_compilation/src/bin/pin_map_sneaky_unsafe_closure.rs")]
+/// ```
+///
+/// The field is [Unpin]:
+/// ```compile_fail
+#[doc = include_str!("../../negative_tests/unpin_stops_compilation/src/bin/pin_map_unpin_field.rs")]
+/// ```
+///
+/// The target is [Unpin] (here because of an explicit `impl Unpin`), so its field must not be
+/// structurally pinned:
+/// ```compile_fail
+#[doc = include_str!("../../negative_tests/unpin_stops_compilation/src/bin/pin_map_unpin_target.rs")]
+/// ```
+///
+/// # Implementation notes
+/// The projection (a closure generated from the path after `=>`, or the given closure) is
+/// passed through [crate::backend::pin_projection_mut] (or [crate::backend::pin_projection_ref])
+/// **outside** `unsafe {...}`. That gives the closure its parameter type (taken from the pin), and
+/// it keeps the closure body outside `unsafe {...}`, too.
+///
+/// The checks for [Unpin] are shared with [crate::prelude::unsafe_pin_new].
+#[doc(hidden)]
+#[macro_export]
+macro_rules! unsafe_pin_map {
+    (@map $map_unchecked:ident $projection:ident $pin:ident $project:expr) => {{
+        $crate::unsafe_pin_new!(@refuse_unpin $pin);
+        let project = $crate::backend::$projection(&$pin, $project);
+        let projected = unsafe { ::core::pin::Pin::$map_unchecked($pin, project) };
+        $crate::unsafe_pin_new!(@refuse_unpin projected);
+        projected
+    }};
+    (mut $pin:expr => $(. $field:tt)+) => {{
+        let pin: ::core::pin::Pin<&mut _> = $pin;
+        $crate::unsafe_pin_map!(@map map_unchecked_mut pin_projection_mut pin
+            |value| &mut value $(. $field)+
+        )
+    }};
+    (mut $pin:expr, $project:expr $(,)?) => {{
+        let pin: ::core::pin::Pin<&mut _> = $pin;
+        $crate::unsafe_pin_map!(@map map_unchecked_mut pin_projection_mut pin $project)
+    }};
+    ($pin:expr => $(. $field:tt)+) => {{
+        let pin: ::core::pin::Pin<&_> = $pin;
+        $crate::unsafe_pin_map!(@map map_unchecked pin_projection_ref pin
+            |value| &value $(. $field)+
+        )
+    }};
+    ($pin:expr, $project:expr $(,)?) => {{
+        let pin: ::core::pin::Pin<&_> = $pin;
+        $crate::unsafe_pin_map!(@map map_unchecked pin_projection_ref pin $project)
+    }};
+}
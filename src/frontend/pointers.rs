@@ -0,0 +1,80 @@
+//! Dereferencing raw pointers, and [core::ptr::NonNull].
+
+/// See [crate::prelude::unsafe_ref].
+///
+/// # Implementation notes
+/// This converts the given pointer (`*const T`, `*mut T` or [core::ptr::NonNull]) to `*const T`
+/// with [crate::backend::RawPointer], **outside** `unsafe {...}`. If `$type` is given, the result
+/// is bound to a local variable of type `*const $type`, so only the identity or an unsizing
+/// coercion compiles. Then it forwards the (converted) pointer, and the lifetime (if any), to
+/// `unsafe_ref` from `prudent-macros-enforce` (or `prudent-macros-lint`).
+///
+/// The arm with both a lifetime and a type comes first. (Otherwise the arm with `$type:ty` would
+/// try to parse the lifetime as a type.)
+///
+/// With `borrowing = ...`, the owner's borrow is evaluated (after the pointer), and the reference
+/// is passed through [crate::backend::borrowing_ref], which shortens its lifetime to that borrow.
+#[doc(hidden)]
+#[macro_export]
+macro_rules! unsafe_ref {
+    ($ptr:expr, borrowing = $owner:expr $(,)?) => {{
+        let ptr = $crate::backend::RawPointer($ptr).into_const_ptr();
+        let owner = $owner;
+        $crate::backend::borrowing_ref(owner, $crate::backend::prudent_macros::unsafe_ref!(ptr))
+    }};
+    ($ptr:expr, $lifetime:lifetime, $type:ty $(,)?) => {{
+        let ptr: *const $type = $crate::backend::RawPointer($ptr).into_const_ptr();
+        $crate::backend::prudent_macros::unsafe_ref!(ptr, $lifetime)
+    }};
+    ($ptr:expr $(, $lifetime:lifetime)?) => {{
+        let ptr = $crate::backend::RawPointer($ptr).into_const_ptr();
+        $crate::backend::prudent_macros::unsafe_ref!(ptr $(, $lifetime)?)
+    }};
+    ($ptr:expr, $type:ty $(,)?) => {{
+        let ptr: *const $type = $crate::backend::RawPointer($ptr).into_const_ptr();
+        $crate::backend::prudent_macros::unsafe_ref!(ptr)
+    }};
+}
+
+/// See [crate::prelude::unsafe_mut].
+///
+/// # Implementation notes
+/// Like [crate::prelude::unsafe_ref], but with `*mut T` (and `*mut $type`), and forwarding to
+/// `unsafe_mut` from `prudent-macros-enforce` (or `prudent-macros-lint`).
+///
+/// There is no `borrowing = ...` arm: see [crate::prelude::unsafe_mut].
+#[doc(hidden)]
+#[macro_export]
+macro_rules! unsafe_mut {
+    ($ptr:expr, $lifetime:lifetime, $type:ty $(,)?) => {{
+        let ptr: *mut $type = $crate::backend::RawPointer($ptr).into_mut_ptr();
+        $crate::backend::prudent_macros::unsafe_mut!(ptr, $lifetime)
+    }};
+    ($ptr:expr $(, $lifetime:lifetime)?) => {{
+        let ptr = $crate::backend::RawPointer($ptr).into_mut_ptr();
+        $crate::backend::prudent_macros::unsafe_mut!(ptr $(, $lifetime)?)
+    }};
+    ($ptr:expr, $type:ty $(,)?) => {{
+        let ptr: *mut $type = $crate::backend::RawPointer($ptr).into_mut_ptr();
+        $crate::backend::prudent_macros::unsafe_mut!(ptr)
+    }};
+}
+
+/// See [crate::prelude::unsafe_val].
+///
+/// # Implementation notes
+/// Like [crate::prelude::unsafe_ref]: This converts the given pointer to `*const T` (or, with the
+/// typed form, to `*const $type` by the identity or an unsizing coercion), and then it forwards it
+/// to `unsafe_val` from `prudent-macros-enforce` (or `prudent-macros-lint`).
+#[doc(hidden)]
+#[macro_export]
+macro_rules! unsafe_val {
+    ($ptr:expr) => {{
+        let ptr = $crate::backend::RawPointer($ptr).into_const_ptr();
+        $crate::backend::prudent_macros::unsafe_val!(ptr)
+    }};
+    ($ptr:expr => $type:ty) => {{
+        let ptr: *const $type = $crate::backend::RawPointer($ptr).into_const_ptr();
+        $crate::backend::prudent_macros::unsafe_val!(ptr)
+    }};
+}
@@ -0,0 +1,121 @@
+//! Assigning, or writing, through raw pointers - to the whole pointee, or to its (sub...-)field or
+//! array/slice item.
+
+/// See [crate::prelude::unsafe_set].
+///
+/// # Implementation notes
+/// If there is no path (`=> .field...` or `=> [index]...`) after the pointer, this converts the
+/// given pointer (`*mut T` or [core::ptr::NonNull]) to `*mut T` with [crate::backend::RawPointer],
+/// **outside** `unsafe {...}`. Then it forwards the (converted) pointer, and the rest of input, to
+/// `unsafe_set` from `prudent-macros-enforce` (or `prudent-macros-lint`).
+///
+/// Otherwise this collects the path (up to the comma that separates the value), and it passes the
+/// pointer and the path to [crate::prelude::unsafe_field] (with `mut`). Then it evaluates the
+/// value. A field of a `#[repr(packed)]` struct may be misaligned (and a macro can't tell whether
+/// the struct is packed), so rather than `*place = value`, this reads the old value with
+/// [core::ptr::read_unaligned], writes the new one with [core::ptr::write_unaligned], and then
+/// drops the old value (outside `unsafe {...}`).
+#[doc(hidden)]
+#[macro_export]
+macro_rules! unsafe_set {
+    (@path $ptr:tt [$($path:tt)+] , $value:expr $(,)?) => {{
+        let place = $crate::unsafe_field!(mut $ptr => $($path)+);
+        let value = $value;
+        let old = unsafe {
+            let old = place.read_unaligned();
+            place.write_unaligned(value);
+            old
+        };
+        ::core::mem::drop(old);
+    }};
+    (@path $ptr:tt [$($path:tt)*] $token:tt $($rest:tt)*) => {
+        $crate::unsafe_set!(@path $ptr [$($path)* $token] $($rest)*)
+    };
+    (@path $ptr:tt [$($path:tt)*]) => {
+        ::core::compile_error!("unsafe_set! expects a comma and a value after the path.")
+    };
+    ($ptr:expr => . $($rest:tt)+) => {
+        $crate::unsafe_set!(@path $ptr [.] $($rest)+)
+    };
+    ($ptr:expr => [$($index:tt)+] $($rest:tt)+) => {
+        $crate::unsafe_set!(@path $ptr [[$($index)+]] $($rest)+)
+    };
+    ($ptr:expr, $($rest:tt)+) => {{
+        let ptr = $crate::backend::RawPointer($ptr).into_mut_ptr();
+        $crate::backend::prudent_macros::unsafe_set!(ptr, $($rest)+)
+    }};
+}
+
+/// Write the given value to the location given in the pointer, or to its (sub...-)field or
+/// array/slice item, **without** dropping the old value. Isolate `unsafe {...}` only for
+/// [core::ptr::write]. Evaluate the given pointer, any index expressions, and the value,
+/// **outside** `unsafe {...}`.
+///
+/// Syntax (same as [crate::prelude::unsafe_set]):
+/// - `unsafe_write!(ptr, value)` writes to the whole pointee. `ptr` is `*mut T` or
+///   [core::ptr::NonNull].
+/// - `unsafe_write!(ptr => .field.sub_field[index]..., value)` writes to a field or item. The path
+///   after `=>` has the same syntax as in [crate::prelude::unsafe_field]. No intermediate reference
+///   is created. The field may be misaligned (in a `#[repr(packed)]` struct): this writes with
+///   [core::ptr::write_unaligned].
+///
+/// Use this (rather than [crate::prelude::unsafe_set]) for uninitialized memory, or wherever the
+/// old value must not be dropped. (If the old value needs [Drop], it's leaked.)
+///
+/// ```
+/// use core::mem::MaybeUninit;
+/// use prudent::prelude::unsafe_write;
+///
+/// struct Message {
+///     id: u32,
+///     body: String,
+/// }
+///
+/// fn main() {
+///     let mut message = MaybeUninit::<Message>::uninit();
+///     let pt = message.as_mut_ptr();
+///     unsafe_write!(pt => .id, 1);
+///     // Assigning (with `unsafe_set!`) would drop the uninitialized `String`.
+///     unsafe_write!(pt => .body, String::from("Hi"));
+///     let message = unsafe { message.assume_init() };
+///     assert_eq!(message.body, "Hi");
+///     # assert_eq!(message.id, 1);
+/// }
+/// ```
+///
+/// # Possible violations
+/// The value expression itself is `unsafe`:
+/// ```compile_fail
+#[doc = include_str!("../../negative_tests/sneaky_unsafe_stops_compilation/src/bin/write_sneaky_unsafe_value.rs")]
+/// ```
+///
+/// # Implementation notes
+/// With a path, this uses [crate::prelude::unsafe_field] (with `mut`) to get the field pointer,
+/// and it writes with [core::ptr::write_unaligned] (like [crate::prelude::unsafe_assume_init]
+/// does).
+#[doc(hidden)]
+#[macro_export]
+macro_rules! unsafe_write {
+    (@path $ptr:tt [$($path:tt)+] , $value:expr $(,)?) => {{
+        let place = $crate::unsafe_field!(mut $ptr => $($path)+);
+        let value = $value;
+        unsafe { ::core::ptr::write_unaligned(place, value) }
+    }};
+    (@path $ptr:tt [$($path:tt)*] $token:tt $($rest:tt)*) => {
+        $crate::unsafe_write!(@path $ptr [$($path)* $token] $($rest)*)
+    };
+    (@path $ptr:tt [$($path:tt)*]) => {
+        ::core::compile_error!("unsafe_write! expects a comma and a value after the path.")
+    };
+    ($ptr:expr => . $($rest:tt)+) => {
+        $crate::unsafe_write!(@path $ptr [.] $($rest)+)
+    };
+    ($ptr:expr => [$($index:tt)+] $($rest:tt)+) => {
+        $crate::unsafe_write!(@path $ptr [[$($index)+]] $($rest)+)
+    };
+    ($ptr:expr, $value:expr $(,)?) => {{
+        let ptr = $crate::backend::RawPointer($ptr).into_mut_ptr();
+        let value = $value;
+        unsafe { ::core::ptr::write(ptr, value) }
+    }};
+}
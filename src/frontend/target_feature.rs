@@ -0,0 +1,60 @@
+//! Invoking `#[target_feature(enable = "...")]` functions.
+
+/// Invoke a `#[target_feature(enable = "...")]` function (or an `unsafe` function), but isolate
+/// `unsafe {...}` only for the function invocation itself. In debug builds, and on `x86` and
+/// `x86_64` only, first check (at runtime) that the CPU supports all the given target features.
+///
+/// Syntax: `unsafe_fn_target_feature!( "feature_1", "feature_2"... => $fn )` or
+/// `unsafe_fn_target_feature!( "feature_1", "feature_2"... => $fn; arg_1, arg_2... )`. The part
+/// after `=>` has the same syntax, and the same checks, as [crate::prelude::unsafe_fn].
+///
+/// Calling a `#[target_feature(enable = "...")]` function from a context that doesn't have those
+/// target features enabled is `unsafe` (even if the function itself is not declared as `unsafe`).
+/// Such functions don't implement [Fn]/[FnMut]/[FnOnce], and they coerce only to `unsafe fn`
+/// pointers. You can invoke them with [crate::prelude::unsafe_fn], too (and that doesn't check the
+/// target features). This macro, on top of that, catches mis-dispatch (for example, calling an
+/// `avx2` function on a CPU without `avx2`) in tests and debug builds.
+///
+/// The check uses `std::is_x86_feature_detected!`, so in debug builds on `x86`/`x86_64` the crate
+/// that invokes this macro needs `std`. A failed check panics. In release builds (without
+/// `debug_assertions`), and on other architectures, there is no check. Unlike
+/// [crate::prelude::unsafe_fn], this can't be used in `const` contexts.
+///
+/// ```
+/// use prudent::prelude::unsafe_fn_target_feature;
+///
+/// #[cfg(target_arch = "x86_64")]
+/// #[target_feature(enable = "sse2")]
+/// fn sum_sse2(values: &[u32]) -> u32 {
+///     values.iter().sum()
+/// }
+///
+/// fn main() {
+///     #[cfg(target_arch = "x86_64")]
+///     {
+///         let _ = unsafe_fn_target_feature!("sse2" => sum_sse2; &[1, 2, 3]);
+///     }
+/// }
+/// ```
+///
+/// # Implementation notes
+/// The check is injected before the invocation, **outside** `unsafe {...}`. It's conditionally
+/// compiled with `#[cfg(...)]`, which is evaluated in the crate that invokes this macro (not in
+/// `prudent`). Then this forwards the part after `=>` to [crate::prelude::unsafe_fn].
+#[doc(hidden)]
+#[macro_export]
+macro_rules! unsafe_fn_target_feature {
+    ($($feature:tt),+ $(,)? => $($tokens:tt)+) => {{
+        #[cfg(all(debug_assertions, any(target_arch = "x86", target_arch = "x86_64")))]
+        {
+            $(
+                ::core::assert!(
+                    ::std::is_x86_feature_detected!($feature),
+                    "Target feature {} is not available on this CPU.",
+                    $feature
+                );
+            )+
+        }
+        $crate::unsafe_fn!($($tokens)+)
+    }};
+}
@@ -0,0 +1,110 @@
+//! Initializing [core::mem::MaybeUninit].
+
+/// Assume that a [core::mem::MaybeUninit] is initialized, and yield its value. Optionally, first
+/// initialize it field by field. Isolate `unsafe {...}` only for the writes and for
+/// [core::mem::MaybeUninit::assume_init]. Evaluate the given [core::mem::MaybeUninit], and all
+/// field values, **outside** `unsafe {...}`.
+///
+/// Syntax:
+/// - `unsafe_assume_init!(uninit)` for a value of type `MaybeUninit<T>`. Same as `unsafe {
+///   uninit.assume_init() }`, but the type is checked to be [core::mem::MaybeUninit].
+/// - `unsafe_assume_init!(uninit => Struct { field_1: value_1, field_2: value_2... })` for a value
+///   of type `MaybeUninit<Struct>`. Similar to a struct literal. Each value is evaluated (in the
+///   given order), and written to its field through a raw pointer (with [core::ptr::addr_of_mut]),
+///   without creating any reference to the (partially initialized) struct, and without dropping the
+///   old (uninitialized) field value. Then this calls [core::mem::MaybeUninit::assume_init].
+///   `Struct` may be `#[repr(packed)]`.
+///
+/// The second form checks at compile time that all fields of `Struct` are listed (each one exactly
+/// once), and that they are accessible (not private to other modules). Field init shorthand (like
+/// `Struct { len }`) is supported. Fields of tuple structs are listed with their indexes, like
+/// `Pair { 0: left, 1: right }`. Generic arguments need a turbofish, like `Pair::<u8> {...}`.
+///
+/// This is useful for large `#[repr(C)]` structs (for FFI) whose fields are initialized one by one
+/// (for example, where some of them come from foreign code that writes through raw pointers).
+///
+/// ```
+/// use core::mem::MaybeUninit;
+/// use prudent::prelude::unsafe_assume_init;
+///
+/// #[repr(C)]
+/// struct Header {
+///     magic: [u8; 4],
+///     len: u32,
+///     flags: u16,
+/// }
+///
+/// fn main() {
+///     let payload = [1u8, 2, 3];
+///     let header = unsafe_assume_init!(MaybeUninit::<Header>::uninit() => Header {
+///         magic: *b"PRDT",
+///         len: payload.len() as u32,
+///         flags: 0,
+///     });
+///     assert_eq!(header.len, 3);
+///
+///     let zeroed = unsafe_assume_init!(MaybeUninit::<u64>::zeroed());
+///     assert_eq!(zeroed, 0);
+/// }
+/// ```
+///
+/// A field is missing:
+/// ```compile_fail,E0063
+/// use core::mem::MaybeUninit;
+/// use prudent::prelude::unsafe_assume_init;
+///
+/// struct Point {
+///     x: i32,
+///     y: i32,
+/// }
+///
+/// fn main() {
+///     let _ = unsafe_assume_init!(MaybeUninit::<Point>::uninit() => Point { x: 1 });
+/// }
+/// ```
+///
+/// # Possible violations
+/// A field value expression itself is `unsafe`:
+/// ```compile_fail
+#[doc = include_str!("../../negative_tests/sneaky_unsafe_stops_compilation/src/bin/assume_init_sneaky_unsafe_field_value.rs")]
+/// ```
+///
+/// # Implementation notes
+/// The check that all fields are listed is an ordinary struct literal (with
+/// [core::unreachable] for values), which the compiler verifies. It's in a closure in an inactive
+/// `if false {...}` branch, so it's never evaluated (and it works in `const` contexts, too).
+///
+/// Fields are written with [core::ptr::write_unaligned], because a field of a `#[repr(packed)]`
+/// struct may be misaligned (and a macro can't tell whether the struct is packed).
+#[doc(hidden)]
+#[macro_export]
+macro_rules! unsafe_assume_init {
+    // Field init shorthand, like `Struct { field }`.
+    (@value $field:ident) => {
+        $field
+    };
+    (@value $field:tt $value:expr) => {
+        $value
+    };
+    ($uninit:expr => $struct:path { $($field:tt $(: $value:expr)?),+ $(,)? }) => {{
+        let mut uninit: ::core::mem::MaybeUninit<$struct> = $uninit;
+        if false {
+            #[allow(unreachable_code)]
+            let _ = || -> $struct {
+                $struct {
+                    $($field: ::core::unreachable!(),)+
+                }
+            };
+        }
+        let ptr = uninit.as_mut_ptr();
+        $(
+            let value = $crate::unsafe_assume_init!(@value $field $($value)?);
+            unsafe { ::core::ptr::addr_of_mut!((*ptr).$field).write_unaligned(value) };
+        )+
+        unsafe { uninit.assume_init() }
+    }};
+    ($uninit:expr) => {{
+        let uninit = $uninit;
+        unsafe { ::core::mem::MaybeUninit::assume_init(uninit) }
+    }};
+}
@@ -0,0 +1,110 @@
+//! Unchecked UTF-8 conversions.
+
+/// Convert bytes to `&str` (or `&mut str`) without checking that they're UTF-8. Isolate `unsafe
+/// {...}` only for [core::str::from_utf8_unchecked] (or [core::str::from_utf8_unchecked_mut]).
+/// Evaluate the given bytes **outside** `unsafe {...}`.
+///
+/// Syntax:
+/// - `unsafe_str!(bytes)` for `bytes` being `&[u8]` (or anything that coerces to it, like `&[u8;
+///   N]` or `&Vec<u8>`). Yield `&str`.
+/// - `unsafe_str!(mut bytes)` for `bytes` being `&mut [u8]` (or anything that coerces to it).
+///   Yield `&mut str`.
+///
+/// In debug builds, this validates the bytes again (with [core::str::from_utf8]). If they're not
+/// UTF-8, it panics with the offset of the first invalid byte, so that tests catch bugs in your
+/// validation.
+///
+/// Usable in `const` contexts (with Rust 1.83+ for `mut`).
+///
+/// For `Vec<u8>` to `String`, see `unsafe_string!` (with feature `alloc`).
+///
+/// ```
+/// use prudent::prelude::unsafe_str;
+///
+/// const NAME: &str = unsafe_str!(b"prudent");
+///
+/// /// Return the leading ASCII letters.
+/// fn word(input: &[u8]) -> &str {
+///     let len = input.iter().take_while(|byte| byte.is_ascii_alphabetic()).count();
+///     // ASCII is valid UTF-8.
+///     unsafe_str!(&input[..len])
+/// }
+///
+/// fn main() {
+///     assert_eq!(word(b"hello, world"), "hello");
+///     assert_eq!(NAME, "prudent");
+///
+///     let mut bytes = *b"shout";
+///     unsafe_str!(mut &mut bytes).make_ascii_uppercase();
+///     assert_eq!(&bytes, b"SHOUT");
+/// }
+/// ```
+///
+/// # Possible violations
+/// The bytes expression itself is `unsafe`:
+/// ```compile_fail
+#[doc = include_str!("../../negative_tests/sneaky_unsafe_stops_compilation/src/bin/str_sneaky_unsafe_bytes.rs")]
+/// ```
+///
+/// # Implementation notes
+/// The validation is done by [crate::backend::expect_utf8], which is a `const fn` (so it works in
+/// `const` contexts, too). It's called under `if cfg!(debug_assertions)`, like
+/// [core::debug_assert].
+#[doc(hidden)]
+#[macro_export]
+macro_rules! unsafe_str {
+    (mut $bytes:expr) => {{
+        let bytes: &mut [u8] = $bytes;
+        if ::core::cfg!(debug_assertions) {
+            $crate::backend::expect_utf8(bytes, "unsafe_str!");
+        }
+        unsafe { ::core::str::from_utf8_unchecked_mut(bytes) }
+    }};
+    ($bytes:expr) => {{
+        let bytes: &[u8] = $bytes;
+        if ::core::cfg!(debug_assertions) {
+            $crate::backend::expect_utf8(bytes, "unsafe_str!");
+        }
+        unsafe { ::core::str::from_utf8_unchecked(bytes) }
+    }};
+}
+
+/// Convert `Vec<u8>` to `String` without checking that it's UTF-8. Isolate `unsafe {...}` only
+/// for `String::from_utf8_unchecked`. Evaluate the given `Vec<u8>` **outside** `unsafe {...}`.
+/// Only with feature `alloc`.
+///
+/// Like [crate::prelude::unsafe_str], in debug builds this validates the bytes again, and it
+/// panics with the offset of the first invalid byte.
+///
+/// ```
+/// use prudent::prelude::unsafe_string;
+///
+/// fn main() {
+///     let mut bytes = b"id-".to_vec();
+///     bytes.extend(42u32.to_string().bytes());
+///     // Both parts are ASCII, hence valid UTF-8.
+///     let id: String = unsafe_string!(bytes);
+///     assert_eq!(id, "id-42");
+/// }
+/// ```
+///
+/// A `&[u8]` is refused (it doesn't transfer the ownership):
+/// ```compile_fail,E0308
+/// use prudent::prelude::unsafe_string;
+///
+/// fn main() {
+///     let _ = unsafe_string!(&b"id"[..]);
+/// }
+/// ```
+#[cfg(feature = "alloc")]
+#[doc(hidden)]
+#[macro_export]
+macro_rules! unsafe_string {
+    ($bytes:expr) => {{
+        let bytes: $crate::backend::alloc::vec::Vec<u8> = $bytes;
+        if ::core::cfg!(debug_assertions) {
+            $crate::backend::expect_utf8(&bytes, "unsafe_string!");
+        }
+        unsafe { $crate::backend::alloc::string::String::from_utf8_unchecked(bytes) }
+    }};
+}
@@ -68,6 +68,8 @@ compile_error!("If you use features lint_unused_unsafe or lint_unused_unsafe_all
 
 pub mod backend;
 
+mod frontend;
+
 pub mod prelude;
 
 /// Even though this module is public, it will **not** be a part of the public API, neither a part
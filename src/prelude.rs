@@ -1,10 +1,6 @@
 //! "Frontend" = user-facing macros. Suggested to be blank imported by the user.
 
-#[cfg(not(feature = "lint_unused_unsafe"))]
-use prudent_macros_enforce as prudent_macros;
-
-#[cfg(feature = "lint_unused_unsafe")]
-use prudent_macros_lint as prudent_macros;
+use crate::backend::prudent_macros;
 // The following will make `cargo build` FAIL. Intentional: use `cargo check` or `cargo check
 // --tests`.
 #[cfg(feature = "lint_unused_unsafe")]
@@ -44,6 +40,36 @@ use no_link as _;
 #[doc = include_str!("../negative_tests/sneaky_unsafe_stops_compilation/src/bin/functn_sneaky_unsafe_arg.rs")]
 /// ```
 ///
+/// The given expression that evaluates to the function pointer (here: an array index) itself is
+/// `unsafe.`
+#[cfg_attr(not(feature = "lint_unused_unsafe"), doc = " ```compile_fail")]
+#[cfg_attr(feature = "lint_unused_unsafe", doc = " ```ignore")]
+#[doc = include_str!("../negative_tests/sneaky_unsafe_stops_compilation/src/bin/functn_sneaky_unsafe_fn_expr_index.rs")]
+/// ```
+///
+/// ## Safe function pointers
+/// If `$fn` is an expression (rather than a path), the function (pointer) it evaluates to must be
+/// `unsafe`, regardless of the number of arguments. That applies to function pointers stored in
+/// struct fields (`table.init`), in arrays/slices (`handlers[i]`) and in [Option]s
+/// (`maybe_handler.unwrap()`). This check does not depend on feature `lint_unused_unsafe`.
+/// ```compile_fail
+#[doc = include_str!("../negative_tests/safe_fn_stops_compilation/src/bin/functn_safe_fn_ptr_field.rs")]
+/// ```
+///
+/// ```compile_fail
+#[doc = include_str!("../negative_tests/safe_fn_stops_compilation/src/bin/functn_safe_fn_ptr_index.rs")]
+/// ```
+///
+/// ```compile_fail
+#[doc = include_str!("../negative_tests/safe_fn_stops_compilation/src/bin/functn_safe_fn_ptr_option.rs")]
+/// ```
+///
+/// The same applies to pointers to `safe fn` items of an `unsafe extern` block (edition 2024),
+/// while pointers to its `unsafe fn` items are accepted.
+/// ```compile_fail,edition2024
+#[doc = include_str!("../negative_tests/safe_fn_stops_compilation/src/bin/extern_safe_fn_ptr_field.rs")]
+/// ```
+///
 /// The target function is safe, hence no need for `unsafe_fn`. Zero args.
 ///
 /// @TODO this should fail, but it does NOT
@@ -71,6 +97,13 @@ use no_link as _;
 #[cfg_attr(not(feature = "lint_unused_unsafe"), doc = " ```ignore")]
 #[doc = include_str!("../negative_tests/unused_unsafe_fails_lint/src/bin/functn_some_args.rs")]
 /// ```
+///
+/// A `safe fn` item of an `unsafe extern` block (edition 2024) given as a path is treated like a
+/// safe Rust function.
+#[cfg_attr(feature = "lint_unused_unsafe", doc = " ```compile_fail,edition2024")]
+#[cfg_attr(not(feature = "lint_unused_unsafe"), doc = " ```ignore")]
+#[doc = include_str!("../negative_tests/unused_unsafe_fails_lint/src/bin/extern_safe_fn.rs")]
+/// ```
 /// @TODO consider:
 /// ```test_harness
 /// // test_harness -as per https://github.com/rust-lang/rust/issues/148942#issuecomment-3565011334
@@ -115,7 +148,8 @@ use no_link as _;
 ///     unsafe_fn!( return_same_mut_ref; &mut marray )[0] = true;
 /// }
 /// ```
-pub use prudent_macros::unsafe_fn;
+#[doc(inline)]
+pub use crate::unsafe_fn;
 
 /// Invoke an `unsafe` method. For methods that have a receiver parameter (`&self`, `&mut self`,
 /// `self`). For associated functions (implemented for a type but with no receiver) use `unsafe_fn`,
@@ -170,6 +204,30 @@ pub use prudent_macros::unsafe_fn;
 // ```
 pub use prudent_macros::unsafe_method;
 
+#[doc(inline)]
+pub use crate::unsafe_fn_async;
+
+#[doc(inline)]
+pub use crate::unsafe_fn_target_feature;
+
+#[doc(inline)]
+pub use crate::unsafe_asm;
+
+#[doc(inline)]
+pub use crate::unsafe_arith;
+
+#[doc(inline)]
+pub use crate::unsafe_assume_init;
+
+#[doc(inline)]
+pub use crate::unsafe_field;
+
+#[doc(inline)]
+pub use crate::unsafe_container_of;
+
+#[doc(inline)]
+pub use crate::unsafe_method_async;
+
 /// Set a value of a `static mut` variable or its (sub...-)field, but isolate `unsafe {...}` only to
 /// that assignment.
 ///
@@ -224,24 +282,222 @@ pub use prudent_macros::unsafe_method;
 /// ```
 pub use prudent_macros::unsafe_static_set;
 
-/// Deref a pointer (either `const` or `mut`) and yield a read-only reference.
+/// Deref a pointer (either `const` or `mut`, or [core::ptr::NonNull]) and yield a read-only
+/// reference.
 ///
 /// If `$type` is given, it's expected to be the referenced type (NOT the given pointer, NOT a
-/// reference based on the given pointer), and the given pointer is cast to `* const $type`. `$type`
-/// may start with `dyn`. `$type` may be a slice `[...]`.
-pub use prudent_macros::unsafe_ref;
+/// reference based on the given pointer), and the given pointer is coerced to `* const $type`.
+/// `$type` may start with `dyn`. `$type` may be a slice `[...]`.
+///
+/// The typed form does **not** reinterpret memory. It allows only the identity (`$type` is the
+/// pointee type), or an unsizing coercion (for example, from `*const [T; N]` to `*const [T]`, or
+/// from `*const T` to `*const dyn Trait`). For a deliberate reinterpretation, use [unsafe_cast]
+/// instead, so that reviewers can see it.
+///
+/// Both a lifetime and a type may be given, in that order: `unsafe_ref!(pt, 'a, $type)` yields
+/// `&'a $type`.
+///
+/// Instead of a lifetime, the reference may borrow an owner of the pointee:
+/// `unsafe_ref!(pt, borrowing = &owner)`. Then the reference can't outlive that borrow (of
+/// `owner`, or of a field, like `&self.buffer`), and `owner` can't be mutated (nor moved) while the
+/// reference is in use. The owner's borrow is evaluated **outside** `unsafe {...}`, too.
+///
+/// ```
+/// use prudent::prelude::unsafe_ref;
+///
+/// struct Cursor {
+///     buffer: Vec<u8>,
+///     position: *const u8,
+/// }
+/// impl Cursor {
+///     fn current(&self) -> &u8 {
+///         unsafe_ref!(self.position, borrowing = &self.buffer)
+///     }
+/// }
+///
+/// fn main() {
+///     let buffer = vec![1, 2, 3];
+///     let position = buffer[1..].as_ptr();
+///     let cursor = Cursor { buffer, position };
+///     assert_eq!(*cursor.current(), 2);
+/// }
+/// ```
+///
+/// The reference can't outlive the owner:
+/// ```compile_fail,E0597
+/// use prudent::prelude::unsafe_ref;
+///
+/// fn main() {
+///     let reference;
+///     {
+///         let buffer = [1u8, 2];
+///         let pt = buffer.as_ptr();
+///         reference = unsafe_ref!(pt, borrowing = &buffer);
+///     }
+///     assert_eq!(*reference, 1);
+/// }
+/// ```
+///
+/// # Possible violations
+/// The owner expression itself is `unsafe`:
+/// ```compile_fail
+#[doc = include_str!("../negative_tests/sneaky_unsafe_stops_compilation/src/bin/ref_sneaky_unsafe_owner.rs")]
+/// ```
+///
+/// ```
+/// use core::ptr::NonNull;
+/// use prudent::prelude::unsafe_ref;
+///
+/// const B: bool = true;
+/// const NN: NonNull<bool> = NonNull::from_ref(&B);
+///
+/// const _: &bool = unsafe_ref!(NN);
+/// fn main() {
+///     let _: &'static bool = unsafe_ref!(NN, 'static);
+///     let _ = unsafe_ref!(NN, bool);
+/// }
+/// ```
+///
+/// The typed form refuses to reinterpret the pointee:
+/// ```compile_fail,E0308
+/// use prudent::prelude::unsafe_ref;
+///
+/// const BYTES: [u8; 8] = [0; 8];
+/// const PT: *const u8 = &BYTES as *const [u8; 8] as *const u8;
+///
+/// const _: &u64 = unsafe_ref!(PT, u64);
+/// fn main() {}
+/// ```
+#[doc(inline)]
+pub use crate::unsafe_ref;
 
-/// Deref a `mut` pointer and yield a `mut` reference.
+/// Deref a `mut` pointer (or [core::ptr::NonNull]) and yield a `mut` reference.
 ///
 /// Like in [unsafe_ref]: If `$type` is given, it's expected to be the referenced
-/// type (NOT the given pointer, NOT the target reference type) and the given pointer is cast to `*
-/// const $type`. `$type` may start with `dyn`. `$type` may be a slice `[...]`.
-pub use prudent_macros::unsafe_mut;
+/// type (NOT the given pointer, NOT the target reference type) and the given pointer is coerced to
+/// `*mut $type` - by the identity or an unsizing coercion only. `$type` may start with `dyn`.
+/// `$type` may be a slice `[...]`. Both a lifetime and a type may be given:
+/// `unsafe_mut!(pt, 'a, $type)` yields `&'a mut $type`.
+///
+/// Unlike [unsafe_ref], there is no `borrowing = &mut owner` form. When the pointee is stored
+/// inline in `owner` (like an item of an array, or a field of a struct), `&mut owner` and the new
+/// reference would overlap, which is undefined behavior (under both Stacked Borrows and Tree
+/// Borrows). Instead, give a lifetime (`unsafe_mut!(pt, 'a)`), or return the reference from a
+/// method that takes `&mut self`, so that its (elided) lifetime borrows `self`.
+///
+/// So a `borrowing = ...` owner is refused:
+/// ```compile_fail
+/// use prudent::prelude::unsafe_mut;
+///
+/// fn main() {
+///     let mut buffer = [1u8, 2];
+///     let pt = buffer.as_mut_ptr();
+///     let _ = unsafe_mut!(pt, borrowing = &mut buffer);
+/// }
+/// ```
+///
+/// A `*const` pointer is refused:
+/// ```compile_fail,E0599
+/// use prudent::prelude::unsafe_mut;
+///
+/// fn main() {
+///     let b = true;
+///     let pt: *const bool = &b;
+///     let _ = unsafe_mut!(pt);
+/// }
+/// ```
+#[doc(inline)]
+pub use crate::unsafe_mut;
+
+#[doc(inline)]
+pub use crate::unsafe_ref_opt;
+
+#[doc(inline)]
+pub use crate::unsafe_mut_opt;
+
+#[doc(inline)]
+pub use crate::unsafe_cast;
+
+#[doc(inline)]
+pub use crate::unsafe_cstr;
+
+#[doc(inline)]
+pub use crate::unsafe_cstr_opt;
+
+#[doc(inline)]
+pub use crate::unsafe_str;
+
+#[doc(inline)]
+pub use crate::unsafe_extern_static;
+
+#[doc(inline)]
+pub use crate::unsafe_pin_new;
+
+#[doc(inline)]
+pub use crate::unsafe_pin_get_mut;
+
+#[doc(inline)]
+pub use crate::unsafe_pin_map;
+
+#[doc(inline)]
+pub use crate::unsafe_cell_ref;
 
-/// Get a (copy of) value from where the pointer points. For [core::marker::Copy] types only.
-pub use prudent_macros::unsafe_val;
+#[doc(inline)]
+pub use crate::unsafe_cell_mut;
 
-/// Assign the given value to the location given in the pointer.
+#[cfg(feature = "alloc")]
+#[doc(inline)]
+pub use crate::unsafe_string;
+
+#[cfg(feature = "alloc")]
+#[doc(inline)]
+pub use crate::unsafe_from_raw;
+
+#[cfg(feature = "alloc")]
+#[doc(inline)]
+pub use crate::unsafe_from_raw_parts;
+
+#[cfg(feature = "alloc")]
+#[doc(inline)]
+pub use crate::unsafe_increment_strong_count;
+
+#[cfg(feature = "alloc")]
+#[doc(inline)]
+pub use crate::unsafe_alloc;
+
+#[cfg(feature = "alloc")]
+#[doc(inline)]
+pub use crate::unsafe_dealloc;
+
+#[cfg(feature = "alloc")]
+#[doc(inline)]
+pub use crate::unsafe_realloc;
+
+#[cfg(feature = "std")]
+#[doc(inline)]
+pub use crate::unsafe_set_var;
+
+#[cfg(feature = "std")]
+#[doc(inline)]
+pub use crate::unsafe_remove_var;
+
+#[cfg(all(feature = "std", unix))]
+#[doc(inline)]
+pub use crate::unsafe_from_raw_fd;
+
+#[cfg(all(feature = "std", unix))]
+#[doc(inline)]
+pub use crate::unsafe_borrow_raw_fd;
+
+/// Get a (copy of) value from where the pointer (either `const` or `mut`, or [core::ptr::NonNull])
+/// points. For [core::marker::Copy] types only.
+///
+/// If `$type` is given (`unsafe_val!(pt => $type)`), the pointer is coerced to `*const $type` - by
+/// the identity coercion only (because the value must be [Sized]).
+#[doc(inline)]
+pub use crate::unsafe_val;
+
+/// Assign the given value to the location given in the pointer (`mut`, or [core::ptr::NonNull]).
 ///
 /// Needed, because we can't isolate:
 ///
@@ -251,4 +507,45 @@ pub use prudent_macros::unsafe_val;
 /// so nothing like:
 ///
 /// `unsafe_set!( pt ) = false;`
-pub use prudent_macros::unsafe_set;
+///
+/// Syntax:
+/// - `unsafe_set!(pt, value)` assigns to the whole pointee.
+/// - `unsafe_set!(pt => .field.sub_field[index]..., value)` assigns to a (sub...-)field or an
+///   array/slice item of the pointee, and to nothing else. The path after `=>` has the same syntax
+///   as in [unsafe_field]. No reference to the whole pointee (nor to any intermediate field) is
+///   created, so this doesn't assert exclusive access to the rest of the pointee. The field may be
+///   misaligned (in a `#[repr(packed)]` struct): the old value is read, and the new one written,
+///   unaligned.
+///
+/// Assigning drops the old value. To write without dropping the old value (for example, to
+/// uninitialized memory), use [unsafe_write].
+///
+/// ```
+/// use prudent::prelude::unsafe_set;
+///
+/// struct Header {
+///     len: usize,
+///     checksums: [u32; 2],
+/// }
+///
+/// fn main() {
+///     let mut header = Header { len: 0, checksums: [0; 2] };
+///     let pt: *mut Header = &mut header;
+///     let bytes = [1u8, 2, 3];
+///     unsafe_set!(pt => .len, bytes.len());
+///     let last = 1;
+///     unsafe_set!(pt => .checksums[last], 6);
+///     assert_eq!((header.len, header.checksums), (3, [0, 6]));
+/// }
+/// ```
+///
+/// # Possible violations
+/// An index expression itself is `unsafe`:
+/// ```compile_fail
+#[doc = include_str!("../negative_tests/sneaky_unsafe_stops_compilation/src/bin/set_sneaky_unsafe_field_index.rs")]
+/// ```
+#[doc(inline)]
+pub use crate::unsafe_set;
+
+#[doc(inline)]
+pub use crate::unsafe_write;
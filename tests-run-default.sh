@@ -18,6 +18,18 @@ cargo test
 echo
 echo "CARGO TEST (release)"
 cargo test --release
+echo
+echo "CARGO TEST (debug, feature alloc)"
+cargo test --features alloc
+echo
+echo "CARGO TEST (release, feature alloc)"
+cargo test --release --features alloc
+echo
+echo "CARGO TEST (debug, feature std)"
+cargo test --features std
+echo
+echo "CARGO TEST (release, feature std)"
+cargo test --release --features std
 
 # Features "lint_unused_unsafe" and "lint_unused_unsafe_all" can't be tested/built, but only
 #   checked; and not in release, but only in debug
@@ -28,6 +40,12 @@ echo
 echo "CARGO CHECK --TESTS (debug, feature lint_unused_unsafe_all)"
 cargo check --tests --features lint_unused_unsafe_all
 
+echo
+echo "CARGO TEST (edition_2024_tests, debug)"
+cd edition_2024_tests
+cargo test
+cd - >/dev/null
+
 echo
 echo "CARGO CHECK --TESTS (negative_tests/verify_error_messages, debug, feature unused_lint)"
 cd negative_tests/verify_error_messages
@@ -15,6 +15,12 @@ echo
 echo "CLIPPY (release)"
 cargo +nightly clippy --release -- -D warnings
 echo
+echo "CLIPPY (debug, feature alloc)"
+cargo +nightly clippy --features alloc -- -D warnings
+echo
+echo "CLIPPY (debug, feature std)"
+cargo +nightly clippy --features std -- -D warnings
+echo
 echo "CLIPPY (debug, feature lint_unused_unsafe)"
 cargo +nightly clippy --features lint_unused_unsafe -- -D warnings
 echo
@@ -37,6 +43,12 @@ cd negative_tests/sneaky_unsafe_stops_compilation/
 cargo +nightly fmt --check
 cd - >/dev/null
 
+echo
+echo "FMT: edition_2024_tests/"
+cd edition_2024_tests/
+cargo +nightly fmt --check
+cd - >/dev/null
+
 echo
 echo "FMT: negative_tests/unused_unsafe_fails_lint/"
 cd negative_tests/unused_unsafe_fails_lint/
@@ -58,6 +70,18 @@ cargo +nightly test
 echo
 echo "CARGO TEST (release)"
 cargo +nightly test --release
+echo
+echo "CARGO TEST (debug, feature alloc)"
+cargo +nightly test --features alloc
+echo
+echo "CARGO TEST (release, feature alloc)"
+cargo +nightly test --release --features alloc
+echo
+echo "CARGO TEST (debug, feature std)"
+cargo +nightly test --features std
+echo
+echo "CARGO TEST (release, feature std)"
+cargo +nightly test --release --features std
 
 # Features "lint_unused_unsafe" and "lint_unused_unsafe_all" can't be tested/built, but only
 #   checked; and not in release, but only in debug
@@ -68,6 +92,12 @@ echo
 echo "CARGO CHECK --TESTS (debug, feature lint_unused_unsafe_all)"
 cargo +nightly check --tests --features lint_unused_unsafe_all
 
+echo
+echo "CARGO TEST (edition_2024_tests, debug)"
+cd edition_2024_tests
+cargo +nightly test
+cd - >/dev/null
+
 echo
 echo "CARGO CHECK --TESTS (negative_tests/verify_error_messages, debug, feature unused_lint)"
 cd negative_tests/verify_error_messages
@@ -81,6 +111,12 @@ echo
 echo "CARGO TEST (MIRI)"
 cargo +nightly miri test
 echo
+echo "CARGO TEST (MIRI, feature alloc)"
+cargo +nightly miri test --features alloc
+echo
+echo "CARGO TEST (MIRI, feature std)"
+cargo +nightly miri test --features std
+echo
 echo "CARGO TEST (MIRI, feature unused_lint)"
 cargo +nightly miri test --features lint_unused_unsafe
 echo
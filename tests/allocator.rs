@@ -0,0 +1,93 @@
+//! `unsafe_alloc!`, `unsafe_dealloc!` and `unsafe_realloc!` (feature `alloc`).
+#![cfg(feature = "alloc")]
+
+use core::alloc::Layout;
+use core::ptr::NonNull;
+use prudent::prelude::{
+    unsafe_alloc, unsafe_dealloc, unsafe_mut, unsafe_realloc, unsafe_val, unsafe_write,
+};
+
+#[test]
+fn layout() {
+    let layout = Layout::array::<u16>(3).unwrap();
+    let ptr = unsafe_alloc!(layout);
+    assert!(!ptr.is_null());
+    assert_eq!(ptr as usize % layout.align(), 0);
+
+    let ptr = unsafe_realloc!(ptr, layout, 12);
+    assert!(!ptr.is_null());
+    let grown = Layout::from_size_align(12, layout.align()).unwrap();
+    unsafe_dealloc!(NonNull::new(ptr).unwrap(), grown);
+}
+
+#[test]
+fn one_item() {
+    #[derive(Clone, Copy)]
+    #[repr(align(32))]
+    struct Aligned(u8);
+    type Block = [u8; 4];
+
+    let ptr = unsafe_alloc!(type Aligned);
+    assert!(!ptr.is_null());
+    assert_eq!(ptr as usize % 32, 0);
+    unsafe_write!(ptr, Aligned(5));
+    assert_eq!(unsafe_val!(ptr => Aligned).0, 5);
+    unsafe_dealloc!(type Aligned, ptr);
+
+    let array = unsafe_alloc!(type Block);
+    unsafe_write!(array, [1, 2, 3, 4]);
+    assert_eq!(unsafe_val!(array), [1, 2, 3, 4]);
+    unsafe_dealloc!(type [u8; 4], array);
+}
+
+#[test]
+fn slices() {
+    let count = 3;
+    let items: NonNull<[u64]> = unsafe_alloc!(type [u64; count]);
+    assert_eq!(items.len(), 3);
+    for (index, item) in (0..count).zip(1u64..) {
+        unsafe_write!(items.as_ptr() => [index], item);
+    }
+
+    let items = unsafe_realloc!(type [u64], items, 5);
+    assert_eq!(items.len(), 5);
+    unsafe_write!(items.as_ptr() => [4], 5);
+    unsafe_write!(items.as_ptr() => [3], 4);
+    assert_eq!(unsafe_mut!(items), &[1, 2, 3, 4, 5]);
+
+    let items = unsafe_realloc!(type [u64], items, 1);
+    assert_eq!(unsafe_mut!(items), &[1]);
+    unsafe_dealloc!(type [u64], items.as_ptr());
+}
+
+#[test]
+#[should_panic(expected = "unsafe_alloc!: capacity overflow.")]
+fn capacity_overflow() {
+    let _ = unsafe_alloc!(type [u64; usize::MAX]);
+}
+
+/// Like `Vec::with_capacity(0)`: no allocation (in release builds, too), but a dangling pointer.
+#[test]
+fn zero_items() {
+    let count = 0;
+    let empty: NonNull<[u64]> = unsafe_alloc!(type [u64; count]);
+    assert_eq!(empty.len(), 0);
+    assert_eq!(empty.cast::<u64>(), NonNull::dangling());
+    assert_eq!(unsafe_mut!(empty), &[]);
+
+    let items = unsafe_realloc!(type [u64], empty, 2);
+    unsafe_write!(items.as_ptr() => [0], 1);
+    unsafe_write!(items.as_ptr() => [1], 2);
+    assert_eq!(unsafe_mut!(items), &[1, 2]);
+
+    let empty = unsafe_realloc!(type [u64], items, count);
+    assert_eq!(empty.len(), 0);
+    unsafe_dealloc!(type [u64], empty);
+}
+
+#[cfg(debug_assertions)]
+#[test]
+#[should_panic(expected = "refuse zero-sized layouts.")]
+fn zero_size_layout() {
+    let _ = unsafe_alloc!(Layout::new::<()>());
+}
@@ -0,0 +1,21 @@
+//! A simple local executor, shared by tests of `Future`s.
+
+// `Waker::noop` is stable since Rust 1.85, newer than our MSRV. See .github/workflows/main.yml.
+#![allow(clippy::incompatible_msrv)]
+
+use core::future::Future;
+use core::pin::pin;
+use core::task::{Context, Poll, Waker};
+
+/// Poll the given future until it's ready. Return its output, and the number of polls.
+pub fn block_on<F: Future>(future: F) -> (F::Output, usize) {
+    let mut future = pin!(future);
+    let mut cx = Context::from_waker(Waker::noop());
+    let mut polls = 0;
+    loop {
+        polls += 1;
+        if let Poll::Ready(output) = future.as_mut().poll(&mut cx) {
+            return (output, polls);
+        }
+    }
+}
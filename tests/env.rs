@@ -0,0 +1,93 @@
+//! `unsafe_set_var!` and `unsafe_remove_var!` (feature `std`).
+//!
+//! Setting environment variables is sound only while no other thread reads them. So this binary
+//! has only one `#[test]`, which runs the cases one after another. (`libtest` runs it in a spawned
+//! thread, while its main thread only waits.) Only the last case spawns another thread.
+#![cfg(feature = "std")]
+
+use prudent::prelude::{unsafe_remove_var, unsafe_set_var};
+use std::env;
+use std::ffi::OsString;
+
+#[test]
+fn env() {
+    set_and_remove();
+    evaluated_once();
+    single_threaded();
+    #[cfg(all(
+        debug_assertions,
+        any(target_os = "linux", target_os = "android"),
+        not(miri)
+    ))]
+    single_threaded_fails_with_other_threads();
+}
+
+fn set_and_remove() {
+    unsafe_set_var!("PRUDENT_TEST_SET_AND_REMOVE", "one");
+    assert_eq!(
+        env::var("PRUDENT_TEST_SET_AND_REMOVE").as_deref(),
+        Ok("one")
+    );
+
+    unsafe_set_var!("PRUDENT_TEST_SET_AND_REMOVE", String::from("two"),);
+    assert_eq!(
+        env::var("PRUDENT_TEST_SET_AND_REMOVE").as_deref(),
+        Ok("two")
+    );
+
+    unsafe_remove_var!("PRUDENT_TEST_SET_AND_REMOVE",);
+    assert!(env::var_os("PRUDENT_TEST_SET_AND_REMOVE").is_none());
+}
+
+fn evaluated_once() {
+    let mut evaluated = Vec::new();
+    unsafe_set_var!(
+        {
+            evaluated.push("key");
+            OsString::from("PRUDENT_TEST_EVALUATED_ONCE")
+        },
+        {
+            evaluated.push("value");
+            "value"
+        }
+    );
+    unsafe_remove_var!({
+        evaluated.push("removed key");
+        "PRUDENT_TEST_EVALUATED_ONCE"
+    });
+    assert_eq!(evaluated, ["key", "value", "removed key"]);
+    assert!(env::var_os("PRUDENT_TEST_EVALUATED_ONCE").is_none());
+}
+
+/// The main thread of `libtest` (which waits for this test) is not counted.
+fn single_threaded() {
+    unsafe_set_var!("PRUDENT_TEST_SINGLE_THREADED", "yes", single_threaded);
+    assert_eq!(
+        env::var("PRUDENT_TEST_SINGLE_THREADED").as_deref(),
+        Ok("yes")
+    );
+    unsafe_remove_var!("PRUDENT_TEST_SINGLE_THREADED", single_threaded);
+    assert!(env::var_os("PRUDENT_TEST_SINGLE_THREADED").is_none());
+}
+
+#[cfg(all(
+    debug_assertions,
+    any(target_os = "linux", target_os = "android"),
+    not(miri)
+))]
+fn single_threaded_fails_with_other_threads() {
+    let (stop, stopped) = std::sync::mpsc::channel::<()>();
+    let other = std::thread::spawn(move || stopped.recv());
+    let result = std::panic::catch_unwind(|| {
+        unsafe_set_var!("PRUDENT_TEST_OTHER_THREADS", "never", single_threaded);
+    });
+    stop.send(()).unwrap();
+    other.join().unwrap().unwrap();
+    assert!(env::var_os("PRUDENT_TEST_OTHER_THREADS").is_none());
+    let message = result.unwrap_err();
+    let message = message.downcast_ref::<String>().unwrap();
+    assert!(
+        message.starts_with("unsafe_set_var!: the process has 3 threads"),
+        "{message}"
+    );
+}
@@ -0,0 +1,113 @@
+//! `unsafe_extern_static!` against real C library (glibc or musl) and linker symbols on Linux, and
+//! against statics exported from this test crate (imitating foreign code).
+
+#[cfg(target_os = "linux")]
+use core::ffi::{c_char, c_int, c_void};
+use prudent::prelude::unsafe_extern_static;
+#[cfg(target_os = "linux")]
+use prudent::prelude::{unsafe_cstr_opt, unsafe_val};
+
+#[cfg(target_os = "linux")]
+extern "C" {
+    static environ: *const *const c_char;
+    static stdin: *mut c_void;
+    static mut optind: c_int;
+    /// Provided by the linker (GNU ld, gold, lld and mold).
+    static __executable_start: u8;
+}
+
+#[cfg(target_os = "linux")]
+#[test]
+fn environ_read() {
+    // Compared with `std::env` (rather than expecting, for example, `PATH`), because under Miri
+    // (with isolation) the environment is empty.
+    let has_path = std::env::var_os("PATH").is_some();
+    let count = std::env::vars_os().count();
+    let variables: *const *const c_char = unsafe_extern_static!(environ);
+    assert!(!variables.is_null());
+    let mut found = false;
+    let mut index = 0;
+    while let Some(variable) = unsafe_cstr_opt!(unsafe_val!(variables.wrapping_add(index))) {
+        found |= variable.to_bytes().starts_with(b"PATH=");
+        index += 1;
+    }
+    assert_eq!(found, has_path);
+    assert_eq!(index, count);
+}
+
+#[cfg(target_os = "linux")]
+#[test]
+#[cfg_attr(miri, ignore)]
+fn libc_and_linker_symbols() {
+    assert!(!unsafe_extern_static!(stdin).is_null());
+
+    let start: &'static u8 = unsafe_extern_static!(ref __executable_start);
+    let start = start as *const u8 as usize;
+    assert!(start != 0);
+    assert!(start <= libc_and_linker_symbols as *const () as usize);
+}
+
+#[cfg(target_os = "linux")]
+#[test]
+#[cfg_attr(miri, ignore)]
+fn libc_write() {
+    // getopt() reads and writes `optind`. Nothing else in this test binary calls getopt(), so
+    // writing back the same value is harmless.
+    let index = unsafe_extern_static!(optind);
+    unsafe_extern_static!(optind = index);
+    assert_eq!(unsafe_extern_static!(optind), index);
+}
+
+#[repr(C)]
+pub struct Table {
+    pub len: usize,
+    pub items: [u16; 4],
+}
+
+/// Imitates foreign code that defines the statics.
+mod foreign {
+    #[no_mangle]
+    pub static PRUDENT_TEST_TABLE: super::Table = super::Table {
+        len: 2,
+        items: [1, 2, 0, 0],
+    };
+
+    #[no_mangle]
+    pub static mut PRUDENT_TEST_MUT_TABLE: super::Table = super::Table {
+        len: 0,
+        items: [0; 4],
+    };
+}
+
+extern "C" {
+    static PRUDENT_TEST_TABLE: Table;
+    static mut PRUDENT_TEST_MUT_TABLE: Table;
+}
+
+#[test]
+#[cfg_attr(miri, ignore)]
+fn projections() {
+    let len = unsafe_extern_static!(PRUDENT_TEST_TABLE => .len);
+    assert_eq!(
+        unsafe_extern_static!(PRUDENT_TEST_TABLE => .items[len - 1]),
+        2
+    );
+    let table: &'static Table = unsafe_extern_static!(ref PRUDENT_TEST_TABLE);
+    assert_eq!(table.items, [1, 2, 0, 0]);
+    let items: &'static [u16; 4] = unsafe_extern_static!(ref PRUDENT_TEST_TABLE => .items);
+    assert_eq!(items[..len], [1, 2]);
+
+    unsafe_extern_static!(PRUDENT_TEST_MUT_TABLE => .items[0] = 7);
+    unsafe_extern_static!(PRUDENT_TEST_MUT_TABLE => .len = 1);
+    assert_eq!(
+        unsafe_extern_static!(PRUDENT_TEST_MUT_TABLE => .items),
+        [7, 0, 0, 0]
+    );
+    unsafe_extern_static!(
+        PRUDENT_TEST_MUT_TABLE = Table {
+            len: 4,
+            items: [4, 3, 2, 1],
+        }
+    );
+    assert_eq!(unsafe_extern_static!(PRUDENT_TEST_MUT_TABLE => .len), 4);
+}
@@ -0,0 +1,100 @@
+//! `unsafe_from_raw_fd!` and `unsafe_borrow_raw_fd!` (feature `std`, Unix only).
+//!
+//! Miri (with isolation) can't open files, pipes or sockets, and it may not support `fcntl`, hence
+//! `cfg_attr(miri, ignore)`.
+
+// `std::io::pipe` is stable since Rust 1.87, newer than our MSRV. See .github/workflows/main.yml.
+#![allow(clippy::incompatible_msrv)]
+#![cfg(all(feature = "std", unix))]
+
+use prudent::prelude::{unsafe_borrow_raw_fd, unsafe_from_raw_fd};
+use std::fs::{self, File};
+use std::io::{self, Read, Write};
+use std::net::{TcpListener, TcpStream};
+use std::os::fd::{AsFd, AsRawFd, BorrowedFd, IntoRawFd, OwnedFd, RawFd};
+
+/// A path in the temporary directory, unique for the given test.
+fn temp_path(test: &str) -> std::path::PathBuf {
+    std::env::temp_dir().join(format!("prudent_test_fd_{}_{test}", std::process::id()))
+}
+
+#[test]
+#[cfg_attr(miri, ignore)]
+fn pipe_into_owned_fd() {
+    let (reader, writer) = io::pipe().unwrap();
+    let (reader, writer) = (reader.into_raw_fd(), writer.into_raw_fd());
+
+    let writer = unsafe_from_raw_fd!(OwnedFd, writer);
+    let mut writer = io::PipeWriter::from(writer);
+    writer.write_all(b"through a pipe").unwrap();
+    drop(writer);
+
+    let mut reader = unsafe_from_raw_fd!(io::PipeReader, reader);
+    let mut content = String::new();
+    reader.read_to_string(&mut content).unwrap();
+    assert_eq!(content, "through a pipe");
+}
+
+#[test]
+#[cfg_attr(miri, ignore)]
+fn tempfile_into_file() {
+    let path = temp_path("tempfile_into_file");
+    let fd = File::create(&path).unwrap().into_raw_fd();
+    let mut file = unsafe_from_raw_fd!(File, fd,);
+    file.write_all(b"prudent").unwrap();
+    drop(file);
+
+    assert_eq!(fs::read_to_string(&path).unwrap(), "prudent");
+    fs::remove_file(path).unwrap();
+}
+
+#[test]
+#[cfg_attr(miri, ignore)]
+fn socket_into_tcp_stream() {
+    let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+    let client = TcpStream::connect(listener.local_addr().unwrap()).unwrap();
+    let (server, _) = listener.accept().unwrap();
+
+    let mut client = unsafe_from_raw_fd!(TcpStream, client.into_raw_fd());
+    client.write_all(b"ping").unwrap();
+    let mut server = server;
+    let mut received = [0u8; 4];
+    server.read_exact(&mut received).unwrap();
+    assert_eq!(&received, b"ping");
+}
+
+#[test]
+#[cfg_attr(miri, ignore)]
+fn borrow() {
+    let (reader, _writer) = io::pipe().unwrap();
+    let raw: RawFd = reader.as_raw_fd();
+
+    let borrowed = unsafe_borrow_raw_fd!(raw);
+    assert_eq!(borrowed.as_raw_fd(), raw);
+
+    fn borrow_for<'a>(_owner: &'a impl AsFd, fd: RawFd) -> BorrowedFd<'a> {
+        unsafe_borrow_raw_fd!(fd, 'a)
+    }
+    let clone = borrow_for(&reader, raw).try_clone_to_owned().unwrap();
+    assert_ne!(clone.as_raw_fd(), raw);
+}
+
+/// No process has this many file descriptors open.
+#[cfg(debug_assertions)]
+const NOT_OPEN: RawFd = RawFd::MAX;
+
+#[cfg(debug_assertions)]
+#[test]
+#[cfg_attr(miri, ignore)]
+#[should_panic(expected = "unsafe_from_raw_fd!: file descriptor 2147483647 is not open.")]
+fn from_raw_fd_not_open() {
+    let _ = unsafe_from_raw_fd!(OwnedFd, NOT_OPEN);
+}
+
+#[cfg(debug_assertions)]
+#[test]
+#[cfg_attr(miri, ignore)]
+#[should_panic(expected = "unsafe_borrow_raw_fd!: file descriptor 2147483647 is not open.")]
+fn borrow_raw_fd_not_open() {
+    let _ = unsafe_borrow_raw_fd!(NOT_OPEN);
+}
@@ -0,0 +1,54 @@
+//! `NonNull` (of sized types, slices and `dyn Trait`) passed to pointer macros.
+
+// `NonNull::from_ref` is newer than our MSRV. See .github/workflows/main.yml.
+#![allow(clippy::incompatible_msrv)]
+
+use core::fmt::Debug;
+use core::ptr::NonNull;
+use prudent::prelude::{unsafe_field, unsafe_mut, unsafe_ref, unsafe_set, unsafe_val};
+
+#[derive(Clone, Copy, Debug, PartialEq)]
+struct Point {
+    x: i32,
+    y: i32,
+}
+
+const POINT: Point = Point { x: 1, y: 2 };
+const POINTS: [Point; 2] = [POINT, Point { x: 3, y: 4 }];
+const NN: NonNull<Point> = NonNull::from_ref(&POINT);
+const NN_SLICE: NonNull<[Point]> = NonNull::from_ref(&POINTS);
+
+const REF: &Point = unsafe_ref!(NN);
+const SLICE: &[Point] = unsafe_ref!(NN_SLICE, [Point]);
+const VALUE: Point = unsafe_val!(NN => Point);
+const Y: *const i32 = unsafe_field!(NN => .y);
+
+#[test]
+fn const_contexts() {
+    assert_eq!(*REF, POINT);
+    assert_eq!(SLICE, &POINTS);
+    assert_eq!(VALUE, POINT);
+    assert_eq!(unsafe_val!(Y), 2);
+}
+
+#[test]
+fn non_const() {
+    let mut point = POINT;
+    let nn = NonNull::from(&mut point);
+    unsafe_mut!(nn).x = 10;
+    unsafe_set!(nn => .y, 20);
+    assert_eq!(unsafe_val!(nn), Point { x: 10, y: 20 });
+    unsafe_set!(nn, POINT);
+    assert_eq!(point, POINT);
+
+    let mut points = POINTS;
+    let nn_slice: NonNull<[Point]> = NonNull::from(&mut points[..]);
+    unsafe_mut!(nn_slice, [Point]).reverse();
+    assert_eq!(points[0], POINTS[1]);
+
+    let nn_dyn: NonNull<dyn Debug> = NonNull::from(&POINT as &dyn Debug);
+    let debug: &dyn Debug = unsafe_ref!(nn_dyn);
+    assert_eq!(format!("{debug:?}"), "Point { x: 1, y: 2 }");
+    let debug: &dyn Debug = unsafe_ref!(nn_dyn, dyn Debug);
+    assert_eq!(format!("{debug:?}"), "Point { x: 1, y: 2 }");
+}
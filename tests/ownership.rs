@@ -0,0 +1,71 @@
+//! `unsafe_from_raw!`, `unsafe_from_raw_parts!` and `unsafe_increment_strong_count!` (feature
+//! `alloc`).
+#![cfg(feature = "alloc")]
+
+use core::fmt::Debug;
+use core::mem::ManuallyDrop;
+use core::ptr::NonNull;
+use prudent::prelude::{unsafe_from_raw, unsafe_from_raw_parts, unsafe_increment_strong_count};
+use std::rc::Rc;
+use std::sync::Arc;
+
+#[test]
+fn boxes() {
+    let raw: *mut dyn Debug = Box::into_raw(Box::new(5u8));
+    let boxed: Box<dyn Debug> = unsafe_from_raw!(Box, raw);
+    assert_eq!(format!("{boxed:?}"), "5");
+
+    let non_null = NonNull::from(Box::leak(Box::new([1u8, 2])));
+    assert_eq!(*unsafe_from_raw!(Box, non_null), [1, 2]);
+}
+
+#[test]
+fn rc_and_arc() {
+    let raw = Rc::into_raw(Rc::new(1u32));
+    unsafe_increment_strong_count!(Rc, raw);
+    let first = unsafe_from_raw!(Rc, raw);
+    let second = unsafe_from_raw!(Rc, raw);
+    assert_eq!(Rc::strong_count(&first), 2);
+    drop(second);
+    assert_eq!(Rc::strong_count(&first), 1);
+
+    let raw = NonNull::new(Arc::into_raw(Arc::new(2u32)).cast_mut()).unwrap();
+    unsafe_increment_strong_count!(Arc, raw);
+    let first = unsafe_from_raw!(Arc, raw);
+    assert_eq!(Arc::strong_count(&first), 2);
+    let _second = unsafe_from_raw!(Arc, raw.as_ptr());
+}
+
+#[test]
+fn vec_and_string() {
+    let mut numbers = ManuallyDrop::new(vec![1u64, 2, 3]);
+    let non_null = NonNull::new(numbers.as_mut_ptr()).unwrap();
+    let numbers = unsafe_from_raw_parts!(Vec, non_null, numbers.len(), numbers.capacity());
+    assert_eq!(numbers, [1, 2, 3]);
+
+    let mut greeting = ManuallyDrop::new(String::from("ahoj"));
+    let greeting = unsafe_from_raw_parts!(
+        String,
+        greeting.as_mut_ptr(),
+        greeting.len(),
+        greeting.capacity(),
+    );
+    assert_eq!(greeting, "ahoj");
+}
+
+#[cfg(debug_assertions)]
+#[test]
+#[should_panic(expected = "unsafe_from_raw_parts!: the length is larger than the capacity.")]
+fn length_over_capacity() {
+    // No allocation, so nothing leaks when this panics.
+    let mut empty = ManuallyDrop::new(Vec::<u8>::new());
+    let _ = unsafe_from_raw_parts!(Vec, empty.as_mut_ptr(), 1, empty.capacity());
+}
+
+#[cfg(debug_assertions)]
+#[test]
+#[should_panic(expected = "unsafe_from_raw_parts!: invalid UTF-8 at byte offset 2.")]
+fn string_invalid_utf8() {
+    let mut bytes = ManuallyDrop::new(vec![b'o', b'k', 0xC0]);
+    let _ = unsafe_from_raw_parts!(String, bytes.as_mut_ptr(), bytes.len(), bytes.capacity());
+}
@@ -0,0 +1,133 @@
+//! `unsafe_pin_new!`, `unsafe_pin_get_mut!` and `unsafe_pin_map!`, including `Future` combinators
+//! run by a simple local executor.
+
+mod common;
+
+use common::block_on;
+use core::future::Future;
+use core::marker::PhantomPinned;
+use core::pin::Pin;
+use core::task::{Context, Poll};
+use prudent::prelude::{unsafe_pin_get_mut, unsafe_pin_map, unsafe_pin_new};
+
+/// Return [Poll::Pending] a given number of times, then [Poll::Ready]. It's not [Unpin], like
+/// futures of `async` blocks.
+struct Countdown {
+    remaining: u8,
+    _pinned: PhantomPinned,
+}
+impl Future for Countdown {
+    type Output = u8;
+    fn poll(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<u8> {
+        let this = unsafe_pin_get_mut!(self);
+        if this.remaining == 0 {
+            Poll::Ready(42)
+        } else {
+            this.remaining -= 1;
+            cx.waker().wake_by_ref();
+            Poll::Pending
+        }
+    }
+}
+fn countdown(remaining: u8) -> Countdown {
+    Countdown {
+        remaining,
+        _pinned: PhantomPinned,
+    }
+}
+
+/// Apply a function to the output of a future.
+struct Map<Fut, F> {
+    /// Structurally pinned.
+    future: Fut,
+    /// Not structurally pinned.
+    f: Option<F>,
+}
+impl<Fut: Future, F: FnOnce(Fut::Output) -> T, T> Future for Map<Fut, F> {
+    type Output = T;
+    fn poll(mut self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<T> {
+        let future: Pin<&mut Fut> = unsafe_pin_map!(mut self.as_mut() => .future);
+        match future.poll(cx) {
+            Poll::Ready(output) => {
+                let f = unsafe_pin_get_mut!(self).f.take();
+                Poll::Ready(f.expect("polled after completion")(output))
+            }
+            Poll::Pending => Poll::Pending,
+        }
+    }
+}
+
+/// Run two futures, and return both outputs. Each is polled (only) until it's ready.
+struct Join<A: Future, B: Future> {
+    /// Structurally pinned, and so are the futures in there.
+    futures: (A, B),
+    outputs: (Option<A::Output>, Option<B::Output>),
+}
+impl<A: Future, B: Future> Future for Join<A, B> {
+    type Output = (A::Output, B::Output);
+    fn poll(mut self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Self::Output> {
+        if self.outputs.0.is_none() {
+            if let Poll::Ready(a) = unsafe_pin_map!(mut self.as_mut() => .futures.0).poll(cx) {
+                unsafe_pin_get_mut!(self.as_mut()).outputs.0 = Some(a);
+            }
+        }
+        if self.outputs.1.is_none() {
+            let b = unsafe_pin_map!(mut self.as_mut(), |join| &mut join.futures.1);
+            if let Poll::Ready(b) = b.poll(cx) {
+                unsafe_pin_get_mut!(self.as_mut()).outputs.1 = Some(b);
+            }
+        }
+        match unsafe_pin_get_mut!(self).outputs {
+            (ref mut a @ Some(_), ref mut b @ Some(_)) => {
+                Poll::Ready((a.take().unwrap(), b.take().unwrap()))
+            }
+            _ => Poll::Pending,
+        }
+    }
+}
+
+#[test]
+fn map() {
+    let map = Map {
+        future: countdown(2),
+        f: Some(|n: u8| u32::from(n) * 2),
+    };
+    assert_eq!(block_on(map), (84, 3));
+}
+
+#[test]
+fn join_of_maps() {
+    let join = Join {
+        futures: (
+            countdown(1),
+            Map {
+                future: countdown(3),
+                f: Some(|n: u8| n + 1),
+            },
+        ),
+        outputs: (None, None),
+    };
+    assert_eq!(block_on(join), ((42, 43), 4));
+}
+
+#[test]
+fn new_and_shared_map() {
+    struct Node {
+        value: (u8, PhantomPinned),
+        _pinned: PhantomPinned,
+    }
+    let mut node = Node {
+        value: (7, PhantomPinned),
+        _pinned: PhantomPinned,
+    };
+    let node: Pin<&mut Node> = unsafe_pin_new!(&mut node);
+    let node: Pin<&Node> = node.into_ref();
+
+    let value: Pin<&(u8, PhantomPinned)> = unsafe_pin_map!(node => .value);
+    assert_eq!(value.0, 7);
+    let pinned: Pin<&PhantomPinned> = unsafe_pin_map!(node, |node| &node.value.1);
+    let _ = pinned;
+
+    let boxed = unsafe_pin_new!(Box::new(countdown(0)));
+    assert_eq!(block_on(boxed), (42, 1));
+}
@@ -0,0 +1,102 @@
+//! `unsafe_arith!`: operators rewritten to `unchecked_*` methods.
+
+// The `unchecked_*` methods (and their `const` use) are newer than our MSRV. See
+// .github/workflows/main.yml.
+#![allow(clippy::incompatible_msrv)]
+
+use core::mem::size_of;
+use core::ops::Add;
+use prudent::prelude::unsafe_arith;
+
+const MUL_ADD: u32 = unsafe_arith!(6u32 * 7 + 8);
+
+#[test]
+fn precedence_and_associativity() {
+    let (a, b, c) = (10u32, 4, 3);
+    assert_eq!(unsafe_arith!(a - b - c), 3);
+    assert_eq!(unsafe_arith!(a + b * c), 22);
+    assert_eq!(unsafe_arith!(a * b + c * 2), 46);
+    assert_eq!(unsafe_arith!((a - b) * c), 18);
+    assert_eq!(unsafe_arith!(a * (b + c) - 1), 69);
+    assert_eq!(unsafe_arith!(a + b << c - 1), 56);
+    assert_eq!(unsafe_arith!(a << 2u32 >> 1u32), 20);
+    assert_eq!(MUL_ADD, 50);
+}
+
+#[test]
+fn unary_operators_and_complex_operands() {
+    let values = [5i64, -3];
+    let ptr = &values[1];
+    assert_eq!(unsafe_arith!(values[0] * -values[1]), 15);
+    assert_eq!(unsafe_arith!(values[0] - *ptr * 2), 11);
+    assert_eq!(unsafe_arith!(-values[0] - -1), -4);
+    assert_eq!(unsafe_arith!(values.len() as i64 * 3 - 1), 5);
+    assert_eq!(
+        unsafe_arith!(i64::from(2u8) * values.iter().sum::<i64>()),
+        4
+    );
+}
+
+#[test]
+fn generic_arguments() {
+    let a = 2usize;
+    let nested = [vec![1u8]];
+    assert_eq!(unsafe_arith!(a + Vec::<Vec<u8>>::new().len()), 2);
+    assert_eq!(
+        unsafe_arith!(nested.len() * Vec::<Vec<u8>>::from([vec![], vec![], vec![]]).len()),
+        3
+    );
+    assert_eq!(unsafe_arith!(<u8>::MAX as u32 >> 1u32), 127);
+    assert_eq!(
+        unsafe_arith!(<<u8 as Add>::Output as Default>::default() + 1),
+        1
+    );
+    assert_eq!(
+        unsafe_arith!(size_of::<Option<Box<u8>>>() >> 1u32),
+        size_of::<usize>() / 2
+    );
+}
+
+#[test]
+fn operands_evaluated_once() {
+    let mut calls = 0u32;
+    let mut next = || {
+        calls += 1;
+        calls
+    };
+    assert_eq!(unsafe_arith!(next() * 10 + next()), 12);
+    assert_eq!(calls, 2);
+}
+
+#[test]
+#[cfg_attr(
+    debug_assertions,
+    should_panic(expected = "unsafe_arith!: `+` overflows in `a * 2 + b`.")
+)]
+fn overflow_add_debug() {
+    let (a, b) = (100u8, 60u8);
+    if cfg!(debug_assertions) {
+        let _ = unsafe_arith!(a * 2 + b);
+    }
+}
+
+#[test]
+#[cfg_attr(debug_assertions, should_panic(expected = "`-` overflows in `a - b`."))]
+fn overflow_sub_debug() {
+    let (a, b) = (1usize, 2usize);
+    if cfg!(debug_assertions) {
+        let _ = unsafe_arith!(a - b);
+    }
+}
+
+#[test]
+#[cfg_attr(
+    debug_assertions,
+    should_panic(expected = "`<<` overflows in `a << b`.")
+)]
+fn overflow_shl_debug() {
+    let (a, b) = (1u16, 16u32);
+    if cfg!(debug_assertions) {
+        let _ = unsafe_arith!(a << b);
+    }
+}
@@ -0,0 +1,99 @@
+//! `unsafe_asm!` on `x86_64`. (MIRI doesn't support inline assembly.)
+#![cfg(all(target_arch = "x86_64", not(miri)))]
+
+use prudent::prelude::unsafe_asm;
+
+#[test]
+fn no_operands() {
+    unsafe_asm!("nop");
+    unsafe_asm!("nop", "nop", options(nomem, nostack));
+}
+
+#[test]
+fn in_out() {
+    let x: u64 = 3;
+    let doubled: u64;
+    unsafe_asm!("lea {0}, [{1} + {1}]", out(reg) doubled, in(reg) x);
+    assert_eq!(doubled, 6);
+}
+
+#[test]
+fn inputs_evaluated_once_in_order() {
+    let mut calls = Vec::new();
+    let mut next = |value: u64| {
+        calls.push(value);
+        value
+    };
+    let sum: u64;
+    unsafe_asm!(
+        "mov {sum}, {a}",
+        "add {sum}, {b}",
+        a = in(reg) next(1),
+        b = in(reg) next(2),
+        sum = lateout(reg) sum,
+    );
+    assert_eq!(sum, 3);
+    assert_eq!(calls, [1, 2]);
+}
+
+#[test]
+fn outputs_to_places() {
+    let mut results = [0u64; 2];
+    let index = 1;
+    unsafe_asm!(
+        "mov {0}, 7",
+        "mov {1}, 8",
+        out(reg) results[0],
+        out(reg) results[index],
+    );
+    assert_eq!(results, [7, 8]);
+
+    struct Registers {
+        rax: u64,
+    }
+    let mut registers = Registers { rax: 0 };
+    unsafe_asm!("mov eax, 9", out("eax") registers.rax);
+    assert_eq!(registers.rax, 9);
+
+    unsafe_asm!("mov {}, 1", out(reg) _);
+}
+
+#[test]
+fn inout() {
+    let mut value: u64 = 5;
+    unsafe_asm!("inc {}", inout(reg) value);
+    assert_eq!(value, 6);
+
+    let mut values = [1u64, 2];
+    unsafe_asm!("inc {}", inlateout(reg) values[1]);
+    assert_eq!(values, [1, 3]);
+
+    let source = [10u64];
+    let incremented: u64;
+    unsafe_asm!("inc {}", inout(reg) source[0] => incremented);
+    assert_eq!(incremented, 11);
+
+    unsafe_asm!("inc {}", inlateout(reg) source[0] => _);
+}
+
+const SHIFT: u64 = 2;
+
+extern "C" fn forty_two() -> u64 {
+    42
+}
+
+#[test]
+fn const_sym_clobber_abi() {
+    let mut value: u64 = 1;
+    unsafe_asm!("shl {value}, {shift}", value = inout(reg) value, shift = const SHIFT);
+    assert_eq!(value, 4);
+
+    let result: u64;
+    unsafe_asm!(
+        "call {f}",
+        f = sym forty_two,
+        out("rax") result,
+        clobber_abi("C"),
+    );
+    assert_eq!(result, 42);
+}
@@ -0,0 +1,89 @@
+//! `unsafe_assume_init!`: whole values, and field-wise initialization of structs.
+
+use core::mem::MaybeUninit;
+use prudent::prelude::unsafe_assume_init;
+
+#[repr(C)]
+struct Header {
+    magic: [u8; 4],
+    len: u32,
+    name: String,
+}
+
+#[derive(Debug, PartialEq)]
+struct Pair<T>(T, T);
+
+/// Like a wire format header (or a C struct with `__attribute__((packed))`): `len` and `crc` are
+/// misaligned.
+#[repr(C, packed)]
+struct Packed {
+    kind: u8,
+    len: u32,
+    crc: u64,
+}
+
+mod ffi {
+    #[repr(C)]
+    pub struct Stat {
+        pub size: u64,
+        pub mode: u32,
+    }
+
+    /// Imitates a foreign function that fills in only some fields.
+    pub fn fill_size(stat: *mut Stat) {
+        unsafe { core::ptr::addr_of_mut!((*stat).size).write(4096) };
+    }
+}
+
+#[test]
+fn whole_value() {
+    let value = unsafe_assume_init!(MaybeUninit::new(7u8));
+    assert_eq!(value, 7);
+    let zeroed: u64 = unsafe_assume_init!(MaybeUninit::zeroed());
+    assert_eq!(zeroed, 0);
+}
+
+#[test]
+fn fields_in_given_order() {
+    let mut order = Vec::new();
+    let mut next = |field: &'static str| {
+        order.push(field);
+        order.len() as u32
+    };
+    let header = unsafe_assume_init!(MaybeUninit::<Header>::uninit() => Header {
+        name: String::from("prudent"),
+        len: next("len"),
+        magic: [next("magic") as u8; 4],
+    });
+    assert_eq!(header.name, "prudent");
+    assert_eq!(header.len, 1);
+    assert_eq!(header.magic, [2; 4]);
+    assert_eq!(order, ["len", "magic"]);
+}
+
+#[test]
+fn tuple_struct_generic() {
+    let pair = unsafe_assume_init!(MaybeUninit::uninit() => Pair::<char> { 0: 'a', 1: 'b' });
+    assert_eq!(pair, Pair('a', 'b'));
+}
+
+#[test]
+fn module_path_partially_filled_by_foreign_code() {
+    let mut stat = MaybeUninit::<ffi::Stat>::uninit();
+    ffi::fill_size(stat.as_mut_ptr());
+    // All fields must be listed. So `size` (which is initialized already) is written again.
+    let stat = unsafe_assume_init!(stat => ffi::Stat { size: 4096, mode: 0o644 });
+    assert_eq!((stat.size, stat.mode), (4096, 0o644));
+}
+
+#[test]
+fn packed_struct() {
+    let packed = unsafe_assume_init!(MaybeUninit::<Packed>::uninit() => Packed {
+        kind: 1,
+        len: 0x0102_0304,
+        crc: u64::MAX,
+    });
+    // Copy the fields out: a reference to a packed field doesn't compile.
+    let (kind, len, crc) = (packed.kind, packed.len, packed.crc);
+    assert_eq!((kind, len, crc), (1, 0x0102_0304, u64::MAX));
+}
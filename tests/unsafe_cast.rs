@@ -0,0 +1,106 @@
+//! `unsafe_cast!` for sized types and slices, with and without `mut`.
+
+use prudent::prelude::unsafe_cast;
+
+#[repr(C, align(8))]
+struct Buffer([u8; 16]);
+
+#[repr(C)]
+#[derive(Debug, PartialEq)]
+struct Pair {
+    first: u32,
+    second: u32,
+}
+
+fn buffer() -> Buffer {
+    let mut bytes = [0u8; 16];
+    bytes[..4].copy_from_slice(&1u32.to_ne_bytes());
+    bytes[4..8].copy_from_slice(&2u32.to_ne_bytes());
+    Buffer(bytes)
+}
+
+#[test]
+fn sized() {
+    let mut buffer = buffer();
+    let pt: *const u8 = buffer.0.as_ptr();
+    assert_eq!(
+        unsafe_cast!(pt, u8 => Pair),
+        &Pair {
+            first: 1,
+            second: 2
+        }
+    );
+
+    let pt: *mut [u8; 16] = &mut buffer.0;
+    let pair: &mut Pair = unsafe_cast!(mut pt, [u8; 16] => Pair);
+    pair.second = 3;
+    assert_eq!(buffer.0[4..8], 3u32.to_ne_bytes());
+}
+
+#[test]
+fn slices() {
+    let mut buffer = buffer();
+    let pt: *mut [u8] = &mut buffer.0[..];
+
+    let words: &[u32] = unsafe_cast!(pt, [u8] => [u32; _]);
+    assert_eq!(&words[..2], &[1, 2]);
+    assert_eq!(words.len(), 4);
+
+    let signed: &mut [i8] = unsafe_cast!(mut pt, [u8] => [i8]);
+    assert_eq!(signed.len(), 16);
+    signed[15] = -1;
+
+    let words: &mut [u64] = unsafe_cast!(mut pt, [u8] => [u64; _]);
+    assert_eq!(words.len(), 2);
+    assert_eq!(buffer.0[15], 0xFF);
+}
+
+#[test]
+fn non_null() {
+    let buffer = buffer();
+    let pt = core::ptr::NonNull::from(&buffer.0[..]);
+    let words: &[[u8; 4]] = unsafe_cast!(pt, [u8] => [[u8; 4]; _]);
+    assert_eq!(words.len(), 4);
+}
+
+#[test]
+fn slice_to_sized() {
+    let mut buffer = buffer();
+    let pt: *mut [u8] = &mut buffer.0[..];
+    let pair: &Pair = unsafe_cast!(pt, [u8] => Pair);
+    assert_eq!((pair.first, pair.second), (1, 2));
+
+    let words: &mut [u32; 4] = unsafe_cast!(mut pt, [u8] => [u32; 4]);
+    words[3] = 4;
+    assert_eq!(buffer.0[12..], 4u32.to_ne_bytes());
+}
+
+#[cfg(debug_assertions)]
+#[test]
+#[should_panic(
+    expected = "unsafe_cast!: the slice size (in bytes) is smaller than the size of \
+                           `[u32; 4]`."
+)]
+fn slice_smaller_than_sized() {
+    let buffer = buffer();
+    let pt: *const [u8] = &buffer.0[..3];
+    let _ = unsafe_cast!(pt, [u8] => [u32; 4]);
+}
+
+#[cfg(debug_assertions)]
+#[test]
+#[should_panic(expected = "unsafe_cast!: the pointer is not aligned for `u32`.")]
+fn misaligned() {
+    let buffer = buffer();
+    let pt: *const u8 = buffer.0[1..].as_ptr();
+    let _ = unsafe_cast!(pt, u8 => u32);
+}
+
+#[cfg(debug_assertions)]
+#[test]
+#[should_panic(expected = "is not a multiple of the size of `u32`.")]
+fn length_not_multiple() {
+    let buffer = buffer();
+    let pt: *const [u8] = &buffer.0[..6];
+    let _ = unsafe_cast!(pt, [u8] => [u32; _]);
+}
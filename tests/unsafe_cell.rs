@@ -0,0 +1,113 @@
+//! `unsafe_cell_ref!` and `unsafe_cell_mut!`, including a spin lock shared by threads.
+
+use core::cell::{Cell, UnsafeCell};
+use core::hint;
+use core::ops::{Deref, DerefMut};
+use core::sync::atomic::{AtomicBool, Ordering};
+use prudent::prelude::{unsafe_cell_mut, unsafe_cell_ref};
+
+struct SpinLock<T: ?Sized> {
+    locked: AtomicBool,
+    data: UnsafeCell<T>,
+}
+// SAFETY: `data` is accessed only through a `SpinGuard`, and there is at most one at a time.
+unsafe impl<T: ?Sized + Send> Sync for SpinLock<T> {}
+
+impl<T> SpinLock<T> {
+    const fn new(data: T) -> Self {
+        Self {
+            locked: AtomicBool::new(false),
+            data: UnsafeCell::new(data),
+        }
+    }
+}
+impl<T: ?Sized> SpinLock<T> {
+    fn lock(&self) -> SpinGuard<'_, T> {
+        while self
+            .locked
+            .compare_exchange_weak(false, true, Ordering::Acquire, Ordering::Relaxed)
+            .is_err()
+        {
+            hint::spin_loop();
+        }
+        SpinGuard { lock: self }
+    }
+}
+
+struct SpinGuard<'a, T: ?Sized> {
+    lock: &'a SpinLock<T>,
+}
+impl<'a, T: ?Sized> SpinGuard<'a, T> {
+    /// Keep the lock locked forever, and return a reference to its data.
+    fn leak(guard: Self) -> &'a mut T {
+        let lock = guard.lock;
+        core::mem::forget(guard);
+        unsafe_cell_mut!(&lock.data, 'a)
+    }
+}
+impl<T: ?Sized> Deref for SpinGuard<'_, T> {
+    type Target = T;
+    fn deref(&self) -> &T {
+        unsafe_cell_ref!(&self.lock.data)
+    }
+}
+impl<T: ?Sized> DerefMut for SpinGuard<'_, T> {
+    fn deref_mut(&mut self) -> &mut T {
+        unsafe_cell_mut!(&self.lock.data)
+    }
+}
+impl<T: ?Sized> Drop for SpinGuard<'_, T> {
+    fn drop(&mut self) {
+        self.lock.locked.store(false, Ordering::Release);
+    }
+}
+
+#[test]
+fn spin_lock_shared_by_threads() {
+    static COUNTER: SpinLock<u32> = SpinLock::new(0);
+    std::thread::scope(|scope| {
+        for _ in 0..4 {
+            scope.spawn(|| {
+                for _ in 0..100 {
+                    *COUNTER.lock() += 1;
+                }
+            });
+        }
+    });
+    assert_eq!(*COUNTER.lock(), 400);
+}
+
+#[test]
+fn spin_lock_of_unsized_and_leaked() {
+    let lock: &SpinLock<[u8]> = &SpinLock::new([1, 2, 3]);
+    lock.lock().reverse();
+    assert_eq!(&*lock.lock(), [3, 2, 1]);
+
+    let leaked: &mut [u8] = SpinGuard::leak(lock.lock());
+    leaked[0] = 0;
+    assert!(lock.locked.load(Ordering::Relaxed));
+    assert_eq!(leaked, [0, 2, 1]);
+}
+
+#[test]
+fn cell() {
+    let cell = Cell::new((1u8, 2u8));
+    unsafe_cell_mut!(&cell,).0 = 3;
+    let pair: &(u8, u8) = unsafe_cell_ref!(&cell,);
+    assert_eq!(*pair, (3, 2));
+}
+
+const fn first(cell: &UnsafeCell<[u8; 2]>) -> u8 {
+    unsafe_cell_ref!(cell)[0]
+}
+
+fn with_lifetime<'a>(cell: &'a UnsafeCell<[u8; 2]>) -> &'a [u8; 2] {
+    unsafe_cell_ref!(cell, 'a)
+}
+
+#[test]
+fn const_fn_and_lifetime() {
+    let cell = UnsafeCell::new([7, 8]);
+    assert_eq!(first(&cell), 7);
+    assert_eq!(with_lifetime(&cell), &[7, 8]);
+}
@@ -0,0 +1,94 @@
+//! `unsafe_container_of!` with an intrusive, doubly linked list.
+
+use core::ptr;
+use prudent::prelude::{unsafe_container_of, unsafe_field, unsafe_val};
+
+struct Link {
+    prev: *mut Link,
+    next: *mut Link,
+}
+
+struct Task {
+    id: u32,
+    state: (u8, Link),
+}
+
+struct Pair(u16, u64);
+
+const PAIR: Pair = Pair(1, 2);
+const PAIR_PTR: *const Pair = &PAIR;
+// The field pointer is derived from the container pointer (not from a reference to the field), so
+// that it can access the whole container.
+const SECOND: *const u64 = unsafe_field!(PAIR_PTR => .1);
+const PAIR_FROM_SECOND: *const Pair = unsafe_container_of!(SECOND, Pair, 1);
+
+fn new_task(id: u32) -> Task {
+    Task {
+        id,
+        state: (
+            0,
+            Link {
+                prev: ptr::null_mut(),
+                next: ptr::null_mut(),
+            },
+        ),
+    }
+}
+
+/// Link `tasks` into a list, and return a pointer to the first `Link`.
+fn link_all(tasks: &mut [Task]) -> *mut Link {
+    // Like `SECOND`, each link pointer is derived from its container pointer (here, all of them from
+    // one pointer to the whole slice), not from a reference to the field.
+    let count = tasks.len();
+    let first: *mut Task = tasks.as_mut_ptr();
+    let links: Vec<*mut Link> = (0..count)
+        .map(|i| unsafe_field!(mut first.wrapping_add(i) => .state.1))
+        .collect();
+    for (i, &link) in links.iter().enumerate() {
+        let task: &mut Task = unsafe_container_of!(ref mut link, Task, state.1);
+        task.state.1.prev = if i == 0 {
+            ptr::null_mut()
+        } else {
+            links[i - 1]
+        };
+        task.state.1.next = links.get(i + 1).copied().unwrap_or(ptr::null_mut());
+    }
+    links[0]
+}
+
+#[test]
+fn traverse_intrusive_list() {
+    let mut tasks = [new_task(10), new_task(20), new_task(30)];
+    let mut link = link_all(&mut tasks);
+    let mut ids = Vec::new();
+    while !link.is_null() {
+        let task: *mut Task = unsafe_container_of!(mut link, Task, state.1);
+        ids.push(unsafe_val!(unsafe_field!(task => .id)));
+        let task: &Task = unsafe_container_of!(ref link, Task, state.1);
+        link = task.state.1.next;
+    }
+    assert_eq!(ids, [10, 20, 30]);
+}
+
+#[test]
+fn const_and_tuple_struct() {
+    // Not compared with `&PAIR`: each use of a `const` may have its own address (and it does under
+    // Miri).
+    assert_eq!(unsafe_val!(unsafe_field!(PAIR_FROM_SECOND => .0)), PAIR.0);
+    assert_eq!(unsafe_val!(unsafe_field!(PAIR_FROM_SECOND => .1)), PAIR.1);
+}
+
+/// `id` must point to the `id` field of one of `tasks`.
+fn task_of_id<'a>(tasks: &'a [Task], id: *const u32) -> &'a Task {
+    let task: &'a Task = unsafe_container_of!(ref id, Task, id, 'a);
+    debug_assert!(tasks.iter().any(|t| ptr::eq(t, task)));
+    task
+}
+
+#[test]
+fn with_lifetime() {
+    let tasks = [new_task(5), new_task(6)];
+    let second: *const Task = &tasks[1];
+    let id = unsafe_field!(second => .id);
+    assert!(ptr::eq(task_of_id(&tasks, id), &tasks[1]));
+}
@@ -0,0 +1,76 @@
+//! `unsafe_cstr!` and `unsafe_cstr_opt!`: pointers to C strings, and byte slices with nul.
+
+// `CStr::from_ptr` is `const` since Rust 1.81, and `<*const T>::is_null` since Rust 1.84, newer
+// than our MSRV. See .github/workflows/main.yml.
+#![allow(clippy::incompatible_msrv)]
+
+use core::ffi::{c_char, CStr};
+use core::ptr::{self, NonNull};
+use prudent::prelude::{unsafe_cstr, unsafe_cstr_opt};
+
+const BYTES: &[u8] = b"const\0";
+const FROM_BYTES: &CStr = unsafe_cstr!(bytes_with_nul = BYTES);
+const FROM_PTR: &CStr = unsafe_cstr!(BYTES.as_ptr(), 'static);
+const NULL: Option<&CStr> = unsafe_cstr_opt!(ptr::null::<c_char>());
+
+#[test]
+fn consts() {
+    assert_eq!(FROM_BYTES.to_bytes(), b"const");
+    assert_eq!(FROM_PTR, FROM_BYTES);
+    assert!(NULL.is_none());
+}
+
+#[test]
+fn pointer_kinds() {
+    let mut bytes = *b"abc\0";
+    let c_chars: *const c_char = bytes.as_ptr().cast();
+    assert_eq!(unsafe_cstr!(c_chars).to_bytes(), b"abc");
+    assert_eq!(unsafe_cstr!(bytes.as_mut_ptr()).to_bytes(), b"abc");
+    // From the rest of the slice (not from `&bytes[1]`), so that it may read up to the nul.
+    let non_null = NonNull::from(&bytes[1..]).cast::<u8>();
+    assert_eq!(unsafe_cstr!(non_null).to_bytes(), b"bc");
+    let signed: *const i8 = bytes.as_ptr().cast();
+    assert_eq!(
+        unsafe_cstr_opt!(signed).map(CStr::to_bytes),
+        Some(&b"abc"[..])
+    );
+}
+
+struct Owned {
+    bytes: Vec<u8>,
+}
+
+impl Owned {
+    fn name(&self) -> &CStr {
+        unsafe_cstr!(self.bytes.as_ptr(), borrowing = &self.bytes)
+    }
+
+    fn name_opt(&self) -> Option<&CStr> {
+        unsafe_cstr_opt!(self.bytes.as_ptr(), borrowing = &self.bytes)
+    }
+}
+
+#[test]
+fn borrowing() {
+    let owned = Owned {
+        bytes: b"owned\0".to_vec(),
+    };
+    assert_eq!(owned.name().to_str(), Ok("owned"));
+    assert_eq!(owned.name_opt(), Some(owned.name()));
+}
+
+#[cfg(debug_assertions)]
+#[test]
+#[should_panic(expected = "unsafe_cstr!: the bytes must end with nul, and contain no other nul.")]
+fn bytes_without_nul() {
+    let bytes = b"no nul".to_vec();
+    let _ = unsafe_cstr!(bytes_with_nul = &bytes);
+}
+
+#[cfg(debug_assertions)]
+#[test]
+#[should_panic(expected = "unsafe_cstr!: the bytes must end with nul, and contain no other nul.")]
+fn bytes_with_interior_nul() {
+    let bytes = b"in\0side\0".to_vec();
+    let _ = unsafe_cstr!(bytes_with_nul = &bytes);
+}
@@ -0,0 +1,76 @@
+//! `unsafe_field!`: raw pointer projections, without intermediate references.
+
+use core::mem::MaybeUninit;
+use prudent::prelude::{unsafe_field, unsafe_val};
+
+#[derive(Clone, Copy)]
+struct Inner {
+    values: [u32; 3],
+    pair: ((u8, u8), u8),
+}
+struct Outer {
+    id: u64,
+    inner: Inner,
+}
+
+const OUTER: Outer = Outer {
+    id: 7,
+    inner: Inner {
+        values: [10, 20, 30],
+        pair: ((1, 2), 3),
+    },
+};
+const OUTER_PTR: *const Outer = &OUTER;
+const VALUE_PTR: *const u32 = unsafe_field!(OUTER_PTR => .inner.values[1]);
+const NESTED_TUPLE_PTR: *const u8 = unsafe_field!(OUTER_PTR => .inner.pair.0.1);
+
+#[test]
+fn const_projection() {
+    // Not compared with `&OUTER...`: each use of a `const` may have its own address (and it does
+    // under Miri). The values are distinct, so they show which field each pointer points to.
+    assert_eq!(unsafe_val!(VALUE_PTR), 20);
+    assert_eq!(unsafe_val!(NESTED_TUPLE_PTR), 2);
+}
+
+#[test]
+fn uninit_fields_written_through_projections() {
+    let mut outer = MaybeUninit::<Outer>::uninit();
+    let outer_ptr = outer.as_mut_ptr();
+    unsafe { unsafe_field!(mut outer_ptr => .id).write(1) };
+    for i in 0..3 {
+        let value = unsafe_field!(mut outer_ptr => .inner.values[i]);
+        unsafe { value.write(i as u32) };
+    }
+    unsafe { unsafe_field!(mut outer_ptr => .inner.pair).write(((4, 5), 6)) };
+    let outer = unsafe { outer.assume_init() };
+    assert_eq!(outer.id, 1);
+    assert_eq!(outer.inner.values, [0, 1, 2]);
+    assert_eq!(outer.inner.pair, ((4, 5), 6));
+}
+
+#[test]
+fn slice_and_index_evaluated_once() {
+    let mut items = [Inner {
+        values: [0; 3],
+        pair: ((0, 0), 0),
+    }; 4];
+    let slice: *mut [Inner] = &mut items[..];
+    let mut calls = 0;
+    let mut next = || {
+        calls += 1;
+        calls
+    };
+    let value = unsafe_field!(mut slice => [next()].values[next()]);
+    unsafe { *value = 9 };
+    assert_eq!(calls, 2);
+    assert_eq!(items[1].values[2], 9);
+}
+
+#[test]
+#[should_panic]
+fn index_out_of_bounds() {
+    let items = [1u8, 2];
+    let slice: *const [u8] = &items[..];
+    let index = items.len();
+    let _ = unsafe_field!(slice => [index]);
+}
@@ -0,0 +1,85 @@
+//! `unsafe_fn_async!` and `unsafe_method_async!`, run by a simple local executor. The futures
+//! yield (return [Poll::Pending]) at their await points, so they get polled more than once, each
+//! time outside `unsafe {...}`.
+
+mod common;
+
+use common::block_on;
+use core::future::Future;
+use core::task::{Context, Poll};
+use prudent::prelude::{unsafe_fn_async, unsafe_method_async};
+
+/// Return [Poll::Pending] once, then [Poll::Ready].
+struct YieldOnce {
+    yielded: bool,
+}
+impl Future for YieldOnce {
+    type Output = ();
+    fn poll(mut self: core::pin::Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<()> {
+        if self.yielded {
+            Poll::Ready(())
+        } else {
+            self.yielded = true;
+            cx.waker().wake_by_ref();
+            Poll::Pending
+        }
+    }
+}
+fn yield_once() -> YieldOnce {
+    YieldOnce { yielded: false }
+}
+
+/// # Safety
+/// `index` must be less than `bytes.len()`.
+async unsafe fn byte_at(bytes: &[u8], index: usize) -> u8 {
+    yield_once().await;
+    unsafe { *bytes.get_unchecked(index) }
+}
+
+struct Counters {
+    counts: [u8; 2],
+}
+impl Counters {
+    /// # Safety
+    /// `index` must be less than 2.
+    async unsafe fn increment(&mut self, index: usize, by: u8) -> u8 {
+        yield_once().await;
+        let count = unsafe { self.counts.get_unchecked_mut(index) };
+        yield_once().await;
+        *count += by;
+        *count
+    }
+}
+
+#[test]
+fn unsafe_fn_async_awaited_outside_unsafe() {
+    let bytes = [10u8, 20, 30];
+    let (sum, polls) = block_on(async {
+        let first = unsafe_fn_async!(byte_at; &bytes, 0).await;
+        let last = unsafe_fn_async!(byte_at; &bytes, bytes.len() - 1).await;
+        first + last
+    });
+    assert_eq!(sum, 40);
+    assert_eq!(polls, 3);
+}
+
+#[test]
+fn unsafe_fn_async_future_created_before_await() {
+    let bytes = [7u8];
+    let future = unsafe_fn_async!(byte_at; &bytes, 0);
+    let (byte, polls) = block_on(future);
+    assert_eq!(byte, 7);
+    assert_eq!(polls, 2);
+}
+
+#[test]
+fn unsafe_method_async_awaited_outside_unsafe() {
+    let mut counters = Counters { counts: [1, 0] };
+    let (count, polls) = block_on(async {
+        unsafe_method_async!(counters =>. increment; 0, 2).await;
+        unsafe_method_async!(counters =>. increment; 0, 3).await
+    });
+    assert_eq!(count, 6);
+    assert_eq!(polls, 5);
+    assert_eq!(counters.counts, [6, 0]);
+}
@@ -0,0 +1,68 @@
+//! `unsafe_fn!` with function pointers stored in struct fields, arrays and [Option]s.
+
+use prudent::prelude::unsafe_fn;
+
+unsafe extern "C" fn init(value: u32) -> u32 {
+    value + 1
+}
+unsafe extern "C" fn finish(value: u32, by: u32) -> u32 {
+    value * by
+}
+unsafe fn double(value: u32) -> u32 {
+    value * 2
+}
+unsafe fn triple(value: u32) -> u32 {
+    value * 3
+}
+unsafe fn zero() -> u32 {
+    0
+}
+
+struct VTable {
+    init: unsafe extern "C" fn(u32) -> u32,
+    finish: unsafe extern "C" fn(u32, u32) -> u32,
+    reset: Option<unsafe fn() -> u32>,
+}
+
+const VTABLE: VTable = VTable {
+    init,
+    finish,
+    reset: Some(zero),
+};
+const HANDLERS: [unsafe fn(u32) -> u32; 2] = [double, triple];
+
+#[test]
+fn field() {
+    let table = &VTABLE;
+    assert_eq!(unsafe_fn!(table.init; 1), 2);
+    assert_eq!(unsafe_fn!(table.finish; 2, 3), 6);
+    assert_eq!(unsafe_fn!(VTABLE.init; 2), 3);
+}
+
+#[test]
+fn index() {
+    let handlers = HANDLERS;
+    for (i, expected) in [(0, 10), (1, 15)] {
+        assert_eq!(unsafe_fn!(handlers[i]; 5), expected);
+    }
+    let handlers: &[unsafe fn(u32) -> u32] = &HANDLERS;
+    assert_eq!(unsafe_fn!(handlers[handlers.len() - 1]; 1), 3);
+}
+
+#[test]
+fn option_unwrapped() {
+    assert_eq!(unsafe_fn!(VTABLE.reset.unwrap()), 0);
+    let maybe_handler: Option<unsafe fn(u32) -> u32> = HANDLERS.first().copied();
+    assert_eq!(unsafe_fn!(maybe_handler.expect("a handler"); 4), 8);
+}
+
+#[test]
+fn callee_evaluated_once() {
+    let mut calls = 0;
+    let mut next_handler = || {
+        calls += 1;
+        HANDLERS[1]
+    };
+    assert_eq!(unsafe_fn!(next_handler(); 2), 6);
+    assert_eq!(calls, 1);
+}
@@ -0,0 +1,87 @@
+//! `unsafe_fn!` and `unsafe_fn_target_feature!` with `#[target_feature(enable = "...")]` functions.
+//! SIMD dispatch as in real code, but with plain Rust bodies.
+#![cfg(target_arch = "x86_64")]
+
+use prudent::prelude::{unsafe_fn, unsafe_fn_target_feature};
+
+#[target_feature(enable = "sse2")]
+fn sum_sse2(values: &[u32]) -> u32 {
+    values.iter().sum()
+}
+
+#[target_feature(enable = "avx2", enable = "fma")]
+fn sum_avx2_fma(values: &[u32]) -> u32 {
+    values.iter().sum()
+}
+
+/// # Safety
+/// `values` must not be empty.
+#[target_feature(enable = "sse2")]
+unsafe fn first_sse2(values: &[u32]) -> u32 {
+    unsafe { *values.get_unchecked(0) }
+}
+
+#[target_feature(enable = "sse2")]
+fn zero_sse2() -> u32 {
+    0
+}
+
+/// No current CPU has both: AMD dropped TBM (with Zen) before it added AVX-512 (with Zen 4), and
+/// Intel never had TBM. So calling this is (practically always) a mis-dispatch.
+#[cfg(debug_assertions)]
+#[target_feature(enable = "tbm", enable = "avx512f")]
+fn sum_tbm_avx512f(values: &[u32]) -> u32 {
+    values.iter().sum()
+}
+
+fn sum_dispatch(values: &[u32]) -> u32 {
+    if is_x86_feature_detected!("avx2") && is_x86_feature_detected!("fma") {
+        unsafe_fn_target_feature!("avx2", "fma" => sum_avx2_fma; values)
+    } else {
+        unsafe_fn_target_feature!("sse2" => sum_sse2; values)
+    }
+}
+
+#[test]
+fn unsafe_fn_with_target_feature_fn() {
+    assert_eq!(unsafe_fn!(sum_sse2; &[1, 2, 3]), 6);
+    assert_eq!(unsafe_fn!(first_sse2; &[4, 5]), 4);
+    assert_eq!(unsafe_fn!(zero_sse2), 0);
+}
+
+#[test]
+fn unsafe_fn_with_target_feature_fn_pointer() {
+    let handlers: [unsafe fn(&[u32]) -> u32; 2] = [sum_sse2, first_sse2];
+    assert_eq!(unsafe_fn!(handlers[0]; &[1, 2]), 3);
+    assert_eq!(unsafe_fn!(handlers[1]; &[1, 2]), 1);
+}
+
+#[test]
+fn unsafe_fn_target_feature_detected() {
+    assert_eq!(unsafe_fn_target_feature!("sse2" => sum_sse2; &[1, 2, 3]), 6);
+    assert_eq!(unsafe_fn_target_feature!("sse2" => zero_sse2), 0);
+    assert_eq!(sum_dispatch(&[1, 2, 3, 4]), 10);
+}
+
+#[test]
+#[cfg(debug_assertions)]
+fn unsafe_fn_target_feature_mis_dispatch_panics() {
+    if is_x86_feature_detected!("tbm") && is_x86_feature_detected!("avx512f") {
+        // Can't mis-dispatch on this CPU.
+        return;
+    }
+    let result = std::panic::catch_unwind(
+        || unsafe_fn_target_feature!("tbm", "avx512f" => sum_tbm_avx512f; &[1, 2]),
+    );
+    let payload = result.unwrap_err();
+    // The message is formatted from literals only, so it may be a `&'static str`.
+    let message = payload
+        .downcast_ref::<&str>()
+        .copied()
+        .or_else(|| payload.downcast_ref::<String>().map(String::as_str))
+        .unwrap();
+    assert!(
+        message.ends_with("is not available on this CPU."),
+        "{message}"
+    );
+}
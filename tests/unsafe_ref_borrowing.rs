@@ -0,0 +1,58 @@
+//! `unsafe_ref!(pt, borrowing = ...)`: references that borrow an owner of the pointee. (And the
+//! `mut` counterpart, which borrows `self` through the method's elided lifetime instead.)
+
+use core::ptr::NonNull;
+use prudent::prelude::{unsafe_mut, unsafe_ref};
+
+/// Items are stored in `storage`, and `current` points to one of them.
+///
+/// Not a `Box<[u32]>`: moving a `Box` (into `Self`) would invalidate `current` under Stacked
+/// Borrows.
+struct Ring {
+    storage: Vec<u32>,
+    current: NonNull<u32>,
+}
+
+impl Ring {
+    fn new(items: &[u32]) -> Self {
+        let mut storage = items.to_vec();
+        let current = NonNull::from(&mut storage[0]);
+        Self { storage, current }
+    }
+
+    fn current(&self) -> &u32 {
+        unsafe_ref!(self.current, borrowing = &self.storage)
+    }
+
+    fn current_mut(&mut self) -> &mut u32 {
+        unsafe_mut!(self.current)
+    }
+}
+
+const VALUE: u8 = 7;
+const fn in_const_fn(owner: &u8) -> &u8 {
+    unsafe_ref!(owner as *const u8, borrowing = owner)
+}
+const FROM_CONST_FN: &u8 = in_const_fn(&VALUE);
+
+#[test]
+fn borrowing_fields() {
+    let mut ring = Ring::new(&[1, 2, 3]);
+    assert_eq!(*ring.current(), 1);
+    *ring.current_mut() += 10;
+    assert_eq!(*ring.current(), 11);
+    assert_eq!(ring.storage[0], 11);
+}
+
+#[test]
+fn borrowing_locals() {
+    let values = [1u8, 2, 3];
+    let slice: *const [u8] = &values[1..];
+    let borrowed: &[u8] = unsafe_ref!(slice, borrowing = &values);
+    assert_eq!(borrowed, &[2, 3]);
+}
+
+#[test]
+fn const_fn() {
+    assert_eq!(*FROM_CONST_FN, 7);
+}
@@ -0,0 +1,59 @@
+//! Three-part forms `unsafe_ref!(pt, 'a, Type)` and `unsafe_mut!(pt, 'a, Type)`, for sized, slice
+//! and `dyn` types, in `const` and non-`const` contexts.
+
+use core::fmt::Debug;
+use prudent::prelude::{unsafe_mut, unsafe_ref};
+
+const VALUES: [u16; 3] = [1, 2, 3];
+const ARRAY_PTR: *const [u16; 3] = &VALUES;
+
+const ARRAY: &[u16; 3] = unsafe_ref!(ARRAY_PTR, 'static, [u16; 3]);
+const SLICE: &[u16] = unsafe_ref!(ARRAY_PTR, 'static, [u16]);
+const DEBUG: &dyn Debug = unsafe_ref!(ARRAY_PTR, 'static, dyn Debug);
+
+const fn slice_mut<'a>(pt: *mut [u16; 3]) -> &'a mut [u16] {
+    unsafe_mut!(pt, 'a, [u16])
+}
+
+#[test]
+fn ref_const() {
+    assert_eq!(ARRAY, &VALUES);
+    assert_eq!(SLICE, &VALUES[..]);
+    assert_eq!(format!("{DEBUG:?}"), "[1, 2, 3]");
+}
+
+fn views<'a>(pt: *const [u16; 3]) -> (&'a [u16; 3], &'a [u16], &'a dyn Debug) {
+    (
+        unsafe_ref!(pt, 'a, [u16; 3]),
+        unsafe_ref!(pt, 'a, [u16]),
+        unsafe_ref!(pt, 'a, dyn Debug),
+    )
+}
+
+fn sort_and_view<'a>(pt: *mut [u16; 3]) -> &'a mut dyn Debug {
+    unsafe_mut!(pt, 'a, [u16; 3])[0] = 0;
+    unsafe_mut!(pt, 'a, [u16]).sort();
+    unsafe_mut!(pt, 'a, dyn Debug)
+}
+
+#[test]
+fn ref_non_const() {
+    let values = VALUES;
+    let (array, slice, debug) = views(&values);
+    assert_eq!(array, slice);
+    assert_eq!(format!("{debug:?}"), "[1, 2, 3]");
+}
+
+#[test]
+fn mut_const_fn() {
+    let mut values = VALUES;
+    slice_mut(&mut values).reverse();
+    assert_eq!(values, [3, 2, 1]);
+}
+
+#[test]
+fn mut_non_const() {
+    let mut values = [3, 2, 1];
+    let debug = sort_and_view(&mut values);
+    assert_eq!(format!("{debug:?}"), "[0, 1, 2]");
+}
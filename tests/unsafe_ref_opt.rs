@@ -0,0 +1,55 @@
+//! `unsafe_ref_opt!` and `unsafe_mut_opt!` with null and non-null pointers.
+
+// `<*const T>::as_ref` is `const` since Rust 1.84, newer than our MSRV. See
+// .github/workflows/main.yml.
+#![allow(clippy::incompatible_msrv)]
+
+use core::fmt::Debug;
+use core::ptr;
+use prudent::prelude::{unsafe_mut_opt, unsafe_ref_opt};
+
+const VALUES: [u16; 3] = [1, 2, 3];
+const SLICE: *const [u16] = &VALUES as *const [u16];
+const NULL_SLICE: *const [u16] = ptr::slice_from_raw_parts(ptr::null(), 0);
+const FIRST: Option<&u16> = unsafe_ref_opt!(SLICE as *const u16);
+const ALL: Option<&'static [u16]> = unsafe_ref_opt!(SLICE, 'static);
+
+#[test]
+fn ref_forms() {
+    assert_eq!(FIRST, Some(&1));
+    assert_eq!(ALL, Some(&VALUES[..]));
+    assert_eq!(unsafe_ref_opt!(NULL_SLICE), None);
+    assert_eq!(unsafe_ref_opt!(NULL_SLICE, [u16]), None);
+    assert_eq!(unsafe_ref_opt!(SLICE, [u16]).map(<[u16]>::len), Some(3));
+
+    let value = 5i64;
+    let pt: *const i64 = &value;
+    let debug: Option<&dyn Debug> = unsafe_ref_opt!(pt, dyn Debug);
+    assert_eq!(format!("{:?}", debug), "Some(5)");
+    let null: *const i64 = ptr::null();
+    assert!(unsafe_ref_opt!(null, dyn Debug).is_none());
+}
+
+/// Imitates foreign code, which may give a null pointer.
+fn lookup(values: &mut [u32], key: usize) -> *mut u32 {
+    values
+        .get_mut(key)
+        .map_or(ptr::null_mut(), |value| value as *mut u32)
+}
+
+#[test]
+fn mut_forms() {
+    let mut values = [10u32, 20];
+    for key in 0..3 {
+        if let Some(value) = unsafe_mut_opt!(lookup(&mut values, key)) {
+            *value += 1;
+        }
+    }
+    assert_eq!(values, [11, 21]);
+
+    let pt: *mut [u32] = &mut values;
+    let all: Option<&mut [u32]> = unsafe_mut_opt!(pt, [u32]);
+    all.unwrap().reverse();
+    assert_eq!(values, [21, 11]);
+    assert!(unsafe_mut_opt!(ptr::null_mut::<u32>(), 'static).is_none());
+}
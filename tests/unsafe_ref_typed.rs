@@ -0,0 +1,32 @@
+//! Typed forms of `unsafe_ref!`, `unsafe_mut!` and `unsafe_val!`: identity and unsizing coercions.
+
+use core::fmt::Debug;
+use prudent::prelude::{unsafe_mut, unsafe_mut_opt, unsafe_ref, unsafe_ref_opt, unsafe_val};
+
+#[repr(C, align(8))]
+struct Aligned([u8; 8]);
+
+const BYTES: Aligned = Aligned([1, 0, 0, 0, 0, 0, 0, 0]);
+const ARRAY_PTR: *const [u8; 8] = &BYTES.0;
+
+#[test]
+fn unsizing() {
+    let slice: &[u8] = unsafe_ref!(ARRAY_PTR, [u8]);
+    assert_eq!(slice.len(), 8);
+    let debug: &dyn Debug = unsafe_ref!(ARRAY_PTR, dyn Debug);
+    assert_eq!(format!("{debug:?}"), "[1, 0, 0, 0, 0, 0, 0, 0]");
+    assert_eq!(unsafe_ref_opt!(ARRAY_PTR, [u8]).map(<[u8]>::len), Some(8));
+
+    let mut array = [3u8, 2, 1];
+    let pt: *mut [u8; 3] = &mut array;
+    unsafe_mut!(pt, [u8]).sort();
+    unsafe_mut_opt!(pt, [u8]).unwrap()[0] = 0;
+    assert_eq!(array, [0, 2, 3]);
+}
+
+#[test]
+fn explicit_reinterpretation_stays_visible() {
+    // The typed form refuses `*const [u8; 8]` -> `*const u64`. The cast must be written out.
+    let value: u64 = unsafe_val!(ARRAY_PTR.cast::<u64>() => u64);
+    assert_eq!(value, u64::from_ne_bytes(BYTES.0));
+}
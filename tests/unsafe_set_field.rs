@@ -0,0 +1,103 @@
+//! `unsafe_set!` and `unsafe_write!` with a field/index path.
+
+use core::cell::Cell;
+use core::mem::MaybeUninit;
+use prudent::prelude::{unsafe_set, unsafe_write};
+
+struct DropCounter<'a>(&'a Cell<u32>);
+impl Drop for DropCounter<'_> {
+    fn drop(&mut self) {
+        self.0.set(self.0.get() + 1);
+    }
+}
+
+struct Header<'a> {
+    len: usize,
+    lines: [(u16, u16); 3],
+    owner: DropCounter<'a>,
+}
+
+#[test]
+fn assign_field_and_item() {
+    let drops = Cell::new(0);
+    let mut header = Header {
+        len: 0,
+        lines: [(0, 0); 3],
+        owner: DropCounter(&drops),
+    };
+    let pt: *mut Header = &mut header;
+    unsafe_set!(pt => .len, 5);
+    let mut calls = 0;
+    let mut next = || {
+        calls += 1;
+        calls
+    };
+    unsafe_set!(pt => .lines[next()].1, 7,);
+    assert_eq!(calls, 1);
+    unsafe_set!(pt => .owner, DropCounter(&drops));
+    assert_eq!(drops.get(), 1, "the old value was dropped");
+
+    assert_eq!(header.len, 5);
+    assert_eq!(header.lines, [(0, 0), (0, 7), (0, 0)]);
+}
+
+#[test]
+fn assign_slice_item() {
+    let mut values = [1u8, 2, 3];
+    let slice: *mut [u8] = &mut values[..];
+    unsafe_set!(slice => [2], 9);
+    assert_eq!(values, [1, 2, 9]);
+}
+
+#[test]
+fn write_without_drop() {
+    let drops = Cell::new(0);
+    let mut header = MaybeUninit::<Header>::uninit();
+    let pt = header.as_mut_ptr();
+    unsafe_write!(pt => .len, 1);
+    unsafe_write!(pt => .lines, [(1, 2); 3]);
+    unsafe_write!(pt => .owner, DropCounter(&drops));
+    let mut header = unsafe { header.assume_init() };
+    assert_eq!(drops.get(), 0);
+
+    let pt: *mut Header = &mut header;
+    unsafe_write!(pt => .lines[0].0, 3);
+    // The old owner is leaked, not dropped.
+    unsafe_write!(pt => .owner, DropCounter(&drops));
+    assert_eq!(drops.get(), 0);
+    assert_eq!(header.lines[0], (3, 2));
+
+    let mut whole = 0u64;
+    unsafe_write!(&mut whole as *mut u64, 4);
+    assert_eq!(whole, 4);
+}
+
+/// `len` and `owner` are misaligned.
+#[repr(C, packed)]
+struct Packed<'a> {
+    kind: u8,
+    len: u32,
+    owner: DropCounter<'a>,
+}
+
+#[test]
+fn packed_struct() {
+    let drops = Cell::new(0);
+    let mut packed = Packed {
+        kind: 1,
+        len: 0,
+        owner: DropCounter(&drops),
+    };
+    let pt: *mut Packed = &mut packed;
+    unsafe_set!(pt => .len, 5);
+    unsafe_set!(pt => .owner, DropCounter(&drops));
+    assert_eq!(drops.get(), 1, "the old value was dropped");
+    // Copy the fields out: a reference to a packed field doesn't compile.
+    assert_eq!((packed.kind, { packed.len }), (1, 5));
+
+    unsafe_write!(pt => .len, 6);
+    // The old owner is leaked, not dropped.
+    unsafe_write!(pt => .owner, DropCounter(&drops));
+    assert_eq!(drops.get(), 1);
+    assert_eq!({ packed.len }, 6);
+}
@@ -0,0 +1,66 @@
+//! `unsafe_str!` (and `unsafe_string!`, with feature `alloc`), including the debug-mode
+//! re-validation.
+
+// `core::str::from_utf8_unchecked_mut` is `const` since Rust 1.83, newer than our MSRV. See
+// .github/workflows/main.yml.
+#![allow(clippy::incompatible_msrv)]
+
+use prudent::prelude::unsafe_str;
+
+const HELLO: &str = unsafe_str!(b"hello");
+
+const fn upper_first(bytes: &mut [u8]) -> &mut str {
+    bytes[0] = bytes[0].to_ascii_uppercase();
+    unsafe_str!(mut bytes)
+}
+
+#[test]
+fn shared() {
+    assert_eq!(HELLO, "hello");
+    let bytes = "žluťoučký kůň".as_bytes().to_vec();
+    assert_eq!(unsafe_str!(&bytes), "žluťoučký kůň");
+    assert_eq!(unsafe_str!(&bytes[..6]), "žluť");
+}
+
+#[test]
+fn mutable() {
+    let mut bytes = *b"prudent";
+    assert_eq!(upper_first(&mut bytes), "Prudent");
+    unsafe_str!(mut &mut bytes[1..]).make_ascii_uppercase();
+    assert_eq!(&bytes, b"PRUDENT");
+}
+
+#[cfg(debug_assertions)]
+#[test]
+#[should_panic(expected = "unsafe_str!: invalid UTF-8 at byte offset 3.")]
+fn invalid() {
+    let bytes = [b'a', b'b', b'c', 0xFF, b'd'];
+    let _ = unsafe_str!(&bytes);
+}
+
+#[cfg(debug_assertions)]
+#[test]
+#[should_panic(expected = "unsafe_str!: invalid UTF-8 at byte offset 1234.")]
+fn invalid_mut_truncated_sequence() {
+    let mut bytes = vec![b'x'; 1234];
+    bytes.extend("ž".bytes().take(1));
+    let _ = unsafe_str!(mut &mut bytes);
+}
+
+#[cfg(feature = "alloc")]
+mod string {
+    use prudent::prelude::unsafe_string;
+
+    #[test]
+    fn valid() {
+        let string: String = unsafe_string!("kůň".as_bytes().to_vec());
+        assert_eq!(string, "kůň");
+    }
+
+    #[cfg(debug_assertions)]
+    #[test]
+    #[should_panic(expected = "unsafe_string!: invalid UTF-8 at byte offset 0.")]
+    fn invalid() {
+        let _ = unsafe_string!(vec![0x80]);
+    }
+}